@@ -0,0 +1,84 @@
+use crate::bytes::words;
+use crate::u128::SIGNS8;
+use crate::*;
+
+/// Applies `f` to every 16 bytes of `bytes` in place.
+///
+/// The last word is padded with zeros, which every conversion leaves alone.
+#[inline]
+fn convert(bytes: &mut [u8], f: impl Fn(Bits8<u128>) -> Bits8<u128>) {
+    for chunk in bytes.chunks_mut(16) {
+        let mut word = [0; 16];
+        word[..chunk.len()].copy_from_slice(chunk);
+        let Bits(word) = f(Bits8(u128::from_le_bytes(word)));
+        chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+    }
+}
+
+/// Returns `true` if `mask` sets the sign bit of every byte of `bytes`.
+#[inline]
+fn all(bytes: &[u8], mask: impl Fn(Bits8<u128>) -> Bits8<u128>) -> bool {
+    words(bytes).all(|(_, word, valid)| (mask(word).0 | !valid) & SIGNS8 == SIGNS8)
+}
+
+/// Converts every ASCII uppercase letter in `bytes` to lowercase, 16 bytes at a time.
+///
+/// ```
+/// use swar::*;
+///
+/// let mut text = *b"Content-Type: TEXT/HTML";
+/// make_ascii_lowercase(&mut text);
+/// assert_eq!(&text, b"content-type: text/html");
+/// ```
+pub fn make_ascii_lowercase(bytes: &mut [u8]) {
+    convert(bytes, Bits8::to_ascii_lowercase);
+}
+
+/// Converts every ASCII lowercase letter in `bytes` to uppercase, 16 bytes at a time.
+///
+/// ```
+/// use swar::*;
+///
+/// let mut text = *b"Content-Type: text/html";
+/// make_ascii_uppercase(&mut text);
+/// assert_eq!(&text, b"CONTENT-TYPE: TEXT/HTML");
+/// ```
+pub fn make_ascii_uppercase(bytes: &mut [u8]) {
+    convert(bytes, Bits8::to_ascii_uppercase);
+}
+
+/// Returns `true` if every byte is an ASCII digit.
+///
+/// ```
+/// use swar::*;
+///
+/// assert!(all_ascii_digits(b"20240229123456789"));
+/// assert!(!all_ascii_digits(b"2024-02-29"));
+/// ```
+pub fn all_ascii_digits(bytes: &[u8]) -> bool {
+    all(bytes, Bits8::ascii_digit_lane_mask)
+}
+
+/// Returns `true` if every byte is an ASCII letter.
+///
+/// ```
+/// use swar::*;
+///
+/// assert!(all_ascii_alphabetic(b"SuperCaliFragilistic"));
+/// assert!(!all_ascii_alphabetic(b"Super Cali"));
+/// ```
+pub fn all_ascii_alphabetic(bytes: &[u8]) -> bool {
+    all(bytes, Bits8::ascii_alphabetic_lane_mask)
+}
+
+/// Returns `true` if every byte is ASCII whitespace.
+///
+/// ```
+/// use swar::*;
+///
+/// assert!(all_ascii_whitespace(b" \t\r\n                  "));
+/// assert!(!all_ascii_whitespace(b" \t\x0B "));
+/// ```
+pub fn all_ascii_whitespace(bytes: &[u8]) -> bool {
+    all(bytes, Bits8::ascii_whitespace_lane_mask)
+}
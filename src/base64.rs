@@ -0,0 +1,273 @@
+use crate::u128::SIGNS8;
+use crate::*;
+
+/// A base64 alphabet from RFC 4648, which differ only in the last two digits.
+///
+/// Base64 is encoded 48 bits at a time: the 6-bit fields are spread into bytes,
+/// and lane compares pick the offset that takes each field to its digit, so no
+/// tables are needed.
+///
+/// ```
+/// use swar::*;
+///
+/// let mut text = [0; 8];
+/// Base64::Standard.encode_slice(b"\xDE\xAD\xBE\xEF", &mut text);
+/// assert_eq!(&text, b"3q2+7w==");
+/// Base64::UrlSafe.encode_slice(b"\xDE\xAD\xBE\xEF", &mut text);
+/// assert_eq!(&text, b"3q2-7w==");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Base64 {
+    /// Uses `+` and `/` for 62 and 63.
+    Standard,
+    /// Uses `-` and `_` for 62 and 63, which are safe in URLs and file names.
+    UrlSafe,
+}
+
+/// Moves the eight 6-bit fields of the low 48 bits of `x` into the low 6 bits of
+/// each byte.
+#[inline]
+const fn spread6(x: u64) -> u64 {
+    let x = (x & 0xFF_FFFF) | (x & 0xFFFF_FF00_0000) << 8;
+    let x = (x & 0x0000_0FFF_0000_0FFF) | (x & 0x00FF_F000_00FF_F000) << 4;
+    (x & 0x003F_003F_003F_003F) | (x & 0x0FC0_0FC0_0FC0_0FC0) << 2
+}
+
+/// Packs the low 6 bits of each byte of `x` into the low 48 bits, which is the
+/// opposite of `spread6`.
+#[inline]
+const fn compress6(x: u64) -> u64 {
+    let x = (x & 0x003F_003F_003F_003F) | (x & 0x3F00_3F00_3F00_3F00) >> 2;
+    let x = (x & 0x0000_0FFF_0000_0FFF) | (x & 0x0FFF_0000_0FFF_0000) >> 4;
+    (x & 0xFF_FFFF) | (x & 0x00FF_FFFF_0000_0000) >> 8
+}
+
+impl Base64 {
+    /// The digits for 62 and 63.
+    #[inline]
+    const fn last_digits(self) -> (u8, u8) {
+        match self {
+            Base64::Standard => (b'+', b'/'),
+            Base64::UrlSafe => (b'-', b'_'),
+        }
+    }
+
+    /// The offset from each field to its digit, which wraps around in the byte,
+    /// for the upper case letters, lower case letters, decimal digits, 62 and 63.
+    #[inline]
+    const fn offsets(self) -> [u8; 5] {
+        let (digit62, digit63) = self.last_digits();
+        [
+            b'A',
+            b'a' - 26,
+            b'0'.wrapping_sub(52),
+            digit62.wrapping_sub(62),
+            digit63.wrapping_sub(63),
+        ]
+    }
+
+    /// The length of the text that encodes `len` bytes, with padding.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// assert_eq!(Base64::encoded_len(0), 0);
+    /// assert_eq!(Base64::encoded_len(4), 8);
+    /// assert_eq!(Base64::encoded_len(6), 8);
+    /// ```
+    #[inline]
+    pub const fn encoded_len(len: usize) -> usize {
+        len.div_ceil(3) * 4
+    }
+
+    /// Encodes the low 48 bits of `chunk` as 8 digits, most significant first.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// assert_eq!(&Base64::Standard.encode_chunk(0x0000_4D61_6E2E_2E2E), b"TWFuLi4u");
+    /// ```
+    #[inline]
+    pub const fn encode_chunk(self, chunk: u64) -> [u8; 8] {
+        let [upper, lower, digit, offset62, offset63] = self.offsets();
+        // Every range past the first adds the difference from the range below it.
+        let fields = Bits8(spread6(chunk) as u128);
+        let offsets = Bits8::from_element(upper as u128)
+            .wrapping_add(above(fields, 25, lower.wrapping_sub(upper)))
+            .wrapping_add(above(fields, 51, digit.wrapping_sub(lower)))
+            .wrapping_add(above(fields, 61, offset62.wrapping_sub(digit)))
+            .wrapping_add(above(fields, 62, offset63.wrapping_sub(offset62)));
+        (fields.wrapping_add(offsets).0 as u64).to_be_bytes()
+    }
+
+    /// Decodes 8 digits, most significant first, into the low 48 bits.
+    ///
+    /// The digits are classified with lane masks, and the offset of each digit's
+    /// range is subtracted from it.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// assert_eq!(Base64::Standard.decode_chunk(*b"TWFuLi4u"), Ok(0x0000_4D61_6E2E_2E2E));
+    /// assert_eq!(
+    ///     Base64::Standard.decode_chunk(*b"TWFu_i4u"),
+    ///     Err(InvalidBase64 { index: 4, byte: b'_' })
+    /// );
+    /// ```
+    #[inline]
+    pub const fn decode_chunk(self, text: [u8; 8]) -> Result<u64, InvalidBase64> {
+        let (digit62, digit63) = self.last_digits();
+        let [upper, lower, digit, offset62, offset63] = self.offsets();
+        // The first digit goes in the highest of the low 8 lanes.
+        let word = Bits8(u64::from_be_bytes(text) as u128);
+        let ranges = [
+            (word.ascii_uppercase_lane_mask().0, upper),
+            (word.ascii_lowercase_lane_mask().0, lower),
+            (word.ascii_digit_lane_mask().0, digit),
+            (word.eq_lane_mask(digit62).0, offset62),
+            (word.eq_lane_mask(digit63).0, offset63),
+        ];
+        // The ranges don't overlap, so adding up their offsets can't carry.
+        let mut valid = 0;
+        let mut offsets = 0;
+        let mut i = 0;
+        while i < ranges.len() {
+            let (mask, offset) = ranges[i];
+            valid |= mask;
+            offsets += (mask >> 7) * offset as u128;
+            i += 1;
+        }
+        let invalid = !valid as u64 & SIGNS8 as u64;
+        if invalid != 0 {
+            let index = invalid.leading_zeros() as usize / 8;
+            return Err(InvalidBase64 {
+                index,
+                byte: text[index],
+            });
+        }
+        Ok(compress6(word.wrapping_sub(Bits8(offsets)).0 as u64))
+    }
+
+    /// Encodes `bytes` into `text` 6 bytes at a time, padding it with `=`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `text` isn't `encoded_len(bytes.len())` long.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// let mut text = [0; 12];
+    /// Base64::Standard.encode_slice(b"any carna", &mut text);
+    /// assert_eq!(&text, b"YW55IGNhcm5h");
+    /// Base64::Standard.encode_slice(b"any carn", &mut text);
+    /// assert_eq!(&text, b"YW55IGNhcm4=");
+    /// ```
+    pub fn encode_slice(self, bytes: &[u8], text: &mut [u8]) {
+        assert_eq!(
+            text.len(),
+            Self::encoded_len(bytes.len()),
+            "text must be as long as the encoded bytes"
+        );
+        for (chunk, text) in bytes.chunks(6).zip(text.chunks_mut(8)) {
+            let mut word = [0; 8];
+            word[2..2 + chunk.len()].copy_from_slice(chunk);
+            let digits = self.encode_chunk(u64::from_be_bytes(word));
+            // Only the digits with bits from the chunk are kept.
+            let len = (8 * chunk.len()).div_ceil(6);
+            text[..len].copy_from_slice(&digits[..len]);
+            for padding in &mut text[len..] {
+                *padding = b'=';
+            }
+        }
+    }
+
+    /// Decodes `text` into the start of `bytes` 8 digits at a time, and returns how
+    /// many bytes were written.
+    ///
+    /// The padding at the end is optional, but if there is any, it has to fill
+    /// out the last group of 4 digits. The bits of the last digit that don't make
+    /// up a whole byte have to be zero, so that every byte string has only one
+    /// encoding. If there is an invalid byte, `bytes` may have been partly written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is too short for the decoded bytes.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// let mut bytes = [0; 16];
+    /// assert_eq!(Base64::UrlSafe.decode_slice(b"3q2-7w==", &mut bytes), Ok(4));
+    /// assert_eq!(Base64::UrlSafe.decode_slice(b"3q2-7w", &mut bytes), Ok(4));
+    /// assert_eq!(&bytes[..4], b"\xDE\xAD\xBE\xEF");
+    /// assert_eq!(
+    ///     Base64::UrlSafe.decode_slice(b"3q2-7", &mut bytes),
+    ///     Err(InvalidBase64 { index: 4, byte: b'7' })
+    /// );
+    /// assert_eq!(
+    ///     Base64::UrlSafe.decode_slice(b"3q2-7w=", &mut bytes),
+    ///     Err(InvalidBase64 { index: 6, byte: b'=' })
+    /// );
+    /// assert_eq!(
+    ///     Base64::UrlSafe.decode_slice(b"3q2-7x==", &mut bytes),
+    ///     Err(InvalidBase64 { index: 5, byte: b'x' })
+    /// );
+    /// ```
+    pub fn decode_slice(self, text: &[u8], bytes: &mut [u8]) -> Result<usize, InvalidBase64> {
+        let digits_len = text
+            .iter()
+            .position(|&byte| byte == b'=')
+            .unwrap_or(text.len());
+        let (digits, padding) = text.split_at(digits_len);
+        // Padding has to take the place of the 1 or 2 digits missing from the
+        // last group, and nothing else.
+        let padded = padding.is_empty()
+            || text.len().is_multiple_of(4)
+                && padding.len() <= 2
+                && padding.iter().all(|&byte| byte == b'=');
+        if !padded {
+            return Err(InvalidBase64 {
+                index: digits_len,
+                byte: b'=',
+            });
+        }
+        // One digit alone has too few bits for a byte.
+        if digits.len() % 4 == 1 {
+            return Err(InvalidBase64 {
+                index: digits.len() - 1,
+                byte: digits[digits.len() - 1],
+            });
+        }
+        let len = 6 * digits.len() / 8;
+        assert!(
+            bytes.len() >= len,
+            "bytes is too short for the decoded bytes"
+        );
+        for (i, (chunk, bytes)) in digits.chunks(8).zip(bytes[..len].chunks_mut(6)).enumerate() {
+            // Pad with zero digits, which only fill the bytes that are dropped.
+            let mut word = [b'A'; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            let n = self.decode_chunk(word).map_err(|error| InvalidBase64 {
+                index: 8 * i + error.index,
+                ..error
+            })?;
+            // The bits of the digits below the last byte must be clear.
+            let unused = 6 * chunk.len() - 8 * bytes.len();
+            if n >> (48 - 6 * chunk.len()) & ((1 << unused) - 1) != 0 {
+                return Err(InvalidBase64 {
+                    index: 8 * i + chunk.len() - 1,
+                    byte: chunk[chunk.len() - 1],
+                });
+            }
+            bytes.copy_from_slice(&n.to_be_bytes()[2..2 + bytes.len()]);
+        }
+        Ok(len)
+    }
+}
+
+/// Puts `delta` in every lane of `fields` that is above `n`, and 0 in the rest.
+#[inline]
+const fn above(fields: Bits8<u128>, n: u8, delta: u8) -> Bits8<u128> {
+    Bits8(fields.lanes_gt(Bits8::from_element(n as u128)).0 * delta as u128)
+}
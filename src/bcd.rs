@@ -0,0 +1,208 @@
+use crate::u128::{ONES4, SIGNS8};
+use crate::*;
+use core::ops::{Add, Sub};
+
+/// Packed binary-coded decimal, with one decimal digit in each nibble of `N`.
+///
+/// The lowest nibble has the ones digit, so a `Bcd<u64>` has 16 digits and a
+/// `Bcd<u128>` has 32. Adding and subtracting work on every digit at once by
+/// biasing each one by 6 so that it carries at 10 instead of 16, and then taking
+/// the 6 back out of the digits that didn't carry.
+///
+/// ```
+/// use swar::*;
+///
+/// let price = Bcd::<u64>::new(Bits4(0x1999)).unwrap();
+/// let tax = Bcd::<u64>::new(Bits4(0x0160)).unwrap();
+/// assert_eq!((price + tax).digits(), Bits4(0x2159));
+/// assert_eq!((price - tax).to_binary(), 1839);
+/// assert_eq!(&(price + tax).to_ascii(), b"0000000000002159");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Bcd<N = u128>(Bits4<N>);
+
+macro_rules! impl_bcd {
+    ($n:ident, $digits:literal) => {
+        impl Bcd<$n> {
+            /// The number of decimal digits.
+            pub const DIGITS: u32 = $digits;
+
+            /// The largest number that fits, which has a 9 in every digit.
+            pub const MAX: Self = Self(Bits(ONES4 as $n * 9));
+
+            /// Takes the digits from the nibbles of `digits`, returning `None` if
+            /// any nibble is more than 9.
+            #[inline]
+            pub const fn new(digits: Bits4<$n>) -> Option<Self> {
+                let nibbles = Bits4(digits.0 as u128);
+                if nibbles.lanes_gt(Bits4::from_element(9)).0 != 0 {
+                    return None;
+                }
+                Some(Self(digits))
+            }
+
+            /// The digits, one in each nibble.
+            #[inline]
+            pub const fn digits(self) -> Bits4<$n> {
+                self.0
+            }
+
+            /// Adds every digit and returns whether the sum carried out of the top
+            /// digit, in which case the sum wraps around.
+            #[doc = ""]
+            #[doc = "```"]
+            #[doc = "use swar::*;"]
+            #[doc = ""]
+            #[doc = concat!("let max = Bcd::<", stringify!($n), ">::MAX;")]
+            #[doc = concat!("let one = Bcd::<", stringify!($n), ">::from_binary(1).unwrap();")]
+            #[doc = concat!("assert_eq!(max.overflowing_add(one), (Bcd::<", stringify!($n), ">::from_binary(0).unwrap(), true));")]
+            #[doc = "```"]
+            #[inline]
+            pub const fn overflowing_add(self, other: Self) -> (Self, bool) {
+                let biased = self.0 .0 + ONES4 as $n * 6;
+                let (sum, overflow) = biased.overflowing_add(other.0 .0);
+                // The digits that carried have a carry into the bottom bit of the
+                // next digit, which is where the bits of the sum differ from the
+                // sum of the bits.
+                let carries = (biased ^ other.0 .0 ^ sum) >> 4 | (overflow as $n) << ($digits * 4 - 4);
+                let unbiased = sum - (!carries & ONES4 as $n) * 6;
+                (Self(Bits(unbiased)), overflow)
+            }
+
+            /// Subtracts every digit and returns whether the difference borrowed
+            /// from above the top digit, in which case it wraps around.
+            #[doc = ""]
+            #[doc = "```"]
+            #[doc = "use swar::*;"]
+            #[doc = ""]
+            #[doc = concat!("let one = Bcd::<", stringify!($n), ">::from_binary(1).unwrap();")]
+            #[doc = concat!("let zero = Bcd::<", stringify!($n), ">::from_binary(0).unwrap();")]
+            #[doc = concat!("assert_eq!(zero.overflowing_sub(one), (Bcd::<", stringify!($n), ">::MAX, true));")]
+            #[doc = "```"]
+            #[inline]
+            pub const fn overflowing_sub(self, other: Self) -> (Self, bool) {
+                let (difference, overflow) = self.0 .0.overflowing_sub(other.0 .0);
+                // The digits that borrowed wrapped around at 16, so 6 more takes
+                // them to where they would have wrapped around at 10.
+                let borrows = (self.0 .0 ^ other.0 .0 ^ difference) >> 4 | (overflow as $n) << ($digits * 4 - 4);
+                let corrected = difference - (borrows & ONES4 as $n) * 6;
+                (Self(Bits(corrected)), overflow)
+            }
+
+            /// Adds every digit, returning `None` if the sum doesn't fit.
+            #[inline]
+            pub const fn checked_add(self, other: Self) -> Option<Self> {
+                match self.overflowing_add(other) {
+                    (sum, false) => Some(sum),
+                    (_, true) => None,
+                }
+            }
+
+            /// Subtracts every digit, returning `None` if `other` is larger.
+            #[inline]
+            pub const fn checked_sub(self, other: Self) -> Option<Self> {
+                match self.overflowing_sub(other) {
+                    (difference, false) => Some(difference),
+                    (_, true) => None,
+                }
+            }
+
+            /// Converts `n` to decimal, returning `None` if it has too many digits.
+            #[inline]
+            pub const fn from_binary(mut n: $n) -> Option<Self> {
+                let mut digits = 0;
+                let mut i = 0;
+                while i < $digits {
+                    digits |= (n % 10) << (4 * i);
+                    n /= 10;
+                    i += 1;
+                }
+                if n != 0 {
+                    return None;
+                }
+                Some(Self(Bits(digits)))
+            }
+
+            /// Converts the digits to binary.
+            ///
+            /// Every round splits off every other lane and adds the higher one times
+            /// the place value of the lower one.
+            #[inline]
+            pub const fn to_binary(self) -> $n {
+                let (tens, ones) = Bits4(self.0 .0 as u128).split();
+                let (hundreds, ones) = Bits8(tens.0 * 10 + ones.0).split();
+                let (ten_thousands, ones) = Bits16(hundreds.0 * 100 + ones.0).split();
+                let (hundred_millions, ones) = Bits32(ten_thousands.0 * 10000 + ones.0).split();
+                let (high, low) = Bits64(hundred_millions.0 * 100_000_000 + ones.0).split();
+                (high.0 * 10_000_000_000_000_000 + low.0) as $n
+            }
+
+            /// Writes the digits in ASCII, most significant first.
+            ///
+            /// The nibbles are spread into bytes with `halve` and then offset by `'0'`.
+            #[inline]
+            pub const fn to_ascii(self) -> [u8; $digits] {
+                let (high, low) = Bits4(self.0 .0 as u128).halve();
+                let zeros = Bits8::from_element(b'0' as u128).0;
+                let (high, low) = ((high.0 + zeros).to_be_bytes(), (low.0 + zeros).to_be_bytes());
+                let mut ascii = [0; $digits];
+                let mut i = 0;
+                while i < $digits {
+                    ascii[i] = if i + 16 < $digits { high[i + 32 - $digits] } else { low[i + 16 - $digits] };
+                    i += 1;
+                }
+                ascii
+            }
+
+            /// Reads ASCII digits, most significant first, returning `None` if any
+            /// byte isn't a digit.
+            ///
+            /// The digits are packed into nibbles with `union`.
+            #[inline]
+            pub const fn from_ascii(ascii: [u8; $digits]) -> Option<Self> {
+                let mut high = [b'0'; 16];
+                let mut low = [b'0'; 16];
+                let mut i = 0;
+                while i < $digits {
+                    if i + 16 < $digits {
+                        high[i + 32 - $digits] = ascii[i];
+                    } else {
+                        low[i + 16 - $digits] = ascii[i];
+                    }
+                    i += 1;
+                }
+                // The last digit goes in the first lane.
+                let (high, low) = (Bits8(u128::from_be_bytes(high)), Bits8(u128::from_be_bytes(low)));
+                if high.ascii_digit_lane_mask().0 & low.ascii_digit_lane_mask().0 != SIGNS8 {
+                    return None;
+                }
+                let nibbles = Bits8::from_element(0x0F).0;
+                let digits = Bits4::union(Bits8(high.0 & nibbles), Bits8(low.0 & nibbles));
+                Some(Self(Bits(digits.0 as $n)))
+            }
+        }
+
+        impl Add for Bcd<$n> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                let (sum, overflow) = self.overflowing_add(rhs);
+                debug_assert!(!overflow, "attempt to add with overflow");
+                sum
+            }
+        }
+
+        impl Sub for Bcd<$n> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                let (difference, overflow) = self.overflowing_sub(rhs);
+                debug_assert!(!overflow, "attempt to subtract with overflow");
+                difference
+            }
+        }
+    };
+}
+
+impl_bcd!(u64, 16);
+impl_bcd!(u128, 32);
@@ -0,0 +1,152 @@
+use crate::*;
+
+/// Loads the 16 bytes of `bytes` from `start` into a word, padded with zeros past
+/// the end.
+///
+/// The second item sets every bit of the bytes that came from `bytes` so that
+/// matches in the padding can be dropped.
+#[inline]
+pub(crate) fn load(bytes: &[u8], start: usize) -> (Bits8<u128>, u128) {
+    let chunk = &bytes[start..core::cmp::min(start + 16, bytes.len())];
+    let mut word = [0; 16];
+    word[..chunk.len()].copy_from_slice(chunk);
+    let valid = u128::MAX
+        .checked_shr(8 * (16 - chunk.len() as u32))
+        .unwrap_or(0);
+    (Bits8(u128::from_le_bytes(word)), valid)
+}
+
+/// Loads every 16 bytes of `haystack` into a word along with its offset and the
+/// mask of its bytes from `load`.
+#[inline]
+pub(crate) fn words(haystack: &[u8]) -> impl Iterator<Item = (usize, Bits8<u128>, u128)> + '_ {
+    (0..haystack.len()).step_by(16).map(move |offset| {
+        let (word, valid) = load(haystack, offset);
+        (offset, word, valid)
+    })
+}
+
+/// Finds the index of the first `byte` in `haystack`, 16 bytes at a time.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(find_byte(b"the quick brown fox jumps", b'j'), Some(20));
+/// assert_eq!(find_byte(b"the quick brown fox jumps", b'z'), None);
+/// ```
+pub fn find_byte(haystack: &[u8], byte: u8) -> Option<usize> {
+    words(haystack).find_map(|(offset, word, valid)| {
+        (word.eq_lane_mask(byte) & valid)
+            .first_match_index()
+            .map(|i| offset + i as usize)
+    })
+}
+
+/// Finds the index of the first byte in `haystack` that is `a`, `b` or `c`.
+///
+/// ```
+/// use swar::*;
+///
+/// let csv = b"name,\"quoted, with comma\"\nnext";
+/// assert_eq!(find_any_of_3_bytes(csv, b'"', b'\n', b'\r'), Some(5));
+/// assert_eq!(find_any_of_3_bytes(&csv[6..], b'"', b'\n', b'\r'), Some(18));
+/// ```
+pub fn find_any_of_3_bytes(haystack: &[u8], a: u8, b: u8, c: u8) -> Option<usize> {
+    words(haystack).find_map(|(offset, word, valid)| {
+        let matches = word.eq_lane_mask(a) | word.eq_lane_mask(b) | word.eq_lane_mask(c);
+        (matches & valid)
+            .first_match_index()
+            .map(|i| offset + i as usize)
+    })
+}
+
+/// The number of 0 or 1 increments that a byte counter can take before it has
+/// to be flushed into a wider one.
+const BYTE_COUNTER_CAPACITY: usize = Padded::<Bits1, 7>::CAPACITY as usize;
+
+/// Adds up the byte counters in every lane of `counters`.
+#[inline]
+fn sum_byte_counters(counters: Bits8<u128>) -> usize {
+    counters
+        .sum_weight2()
+        .sum_weight2()
+        .sum_weight2()
+        .sum_weight2()
+        .0 as usize
+}
+
+/// Counts how many times `needle` appears in `haystack`.
+///
+/// Every 16 bytes add 1 to the byte counter of each lane that matches, and the
+/// counters are added up before any of them can overflow.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(count_bytes(b"one\ntwo\nthree\nfour\nfive\nsix\n", b'\n'), 6);
+/// ```
+pub fn count_bytes(haystack: &[u8], needle: u8) -> usize {
+    haystack
+        .chunks(16 * BYTE_COUNTER_CAPACITY)
+        .map(|block| {
+            sum_byte_counters(words(block).fold(Bits8(0), |counters, (_, word, valid)| {
+                counters + Bits8((word.eq_lane_mask(needle) & valid).0 >> 7)
+            }))
+        })
+        .sum()
+}
+
+/// Counts how many times `byte` appears in `haystack`.
+///
+/// This is the same as `count_bytes`.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(count_byte(b"one\ntwo\nthree\nfour\nfive\nsix\n", b'\n'), 6);
+/// ```
+#[inline]
+pub fn count_byte(haystack: &[u8], byte: u8) -> usize {
+    count_bytes(haystack, byte)
+}
+
+/// Counts how many times every byte value appears in `bytes`.
+///
+/// Each value has a byte counter, and the bytes take turns between 4 sets of
+/// counters so that runs of the same byte don't wait on one counter. Before any
+/// counter can overflow, they are widened with `halve` so that the sets can be
+/// added together, and then flushed into the bins.
+///
+/// ```
+/// use swar::*;
+///
+/// let bins = byte_histogram(b"mississippi");
+/// assert_eq!((bins[b's' as usize], bins[b'i' as usize], bins[b'm' as usize]), (4, 4, 1));
+/// assert_eq!(bins.iter().sum::<usize>(), 11);
+/// ```
+pub fn byte_histogram(bytes: &[u8]) -> [usize; 256] {
+    const SETS: usize = 4;
+    let mut bins = [0; 256];
+    for block in bytes.chunks(SETS * BYTE_COUNTER_CAPACITY) {
+        // The counter for `byte` is lane `byte % 16` of word `byte / 16`.
+        let mut counters = [[Bits8(0); 16]; SETS];
+        for group in block.chunks(SETS) {
+            for (set, &byte) in counters.iter_mut().zip(group) {
+                set[byte as usize / 16] += Bits8(1 << (8 * (byte % 16)));
+            }
+        }
+        for (word, bins) in bins.chunks_mut(16).enumerate() {
+            let (mut high, mut low) = (Bits16(0), Bits16(0));
+            for set in &counters {
+                let (set_high, set_low) = set[word].halve();
+                high += set_high;
+                low += set_low;
+            }
+            for lane in 0..8 {
+                bins[lane as usize] += low.extract(lane) as usize;
+                bins[lane as usize + 8] += high.extract(lane) as usize;
+            }
+        }
+    }
+    bins
+}
@@ -0,0 +1,93 @@
+use crate::u128::SIGNS8;
+use crate::*;
+
+/// Combines the ASCII digits in every byte of `digits` into an 8-digit number in
+/// each 64-bit lane.
+///
+/// Every round splits off every other lane and adds the more significant one,
+/// which comes first, times the place value of the other side.
+#[inline]
+const fn combine_digits(digits: u128) -> Bits64<u128> {
+    let (ones, tens) = Bits8(digits & Bits8::from_element(0x0F).0).split();
+    let (ones, hundreds) = Bits16(tens.0 * 10 + ones.0).split();
+    let (ones, ten_thousands) = Bits32(hundreds.0 * 100 + ones.0).split();
+    Bits64(ten_thousands.0 * 10000 + ones.0)
+}
+
+/// Parses the 8 ASCII digits in `digits`, which has the first digit in the lowest
+/// byte, the same as `u64::from_le_bytes`.
+///
+/// Returns `None` unless every byte is a digit.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(parse_digits8(u64::from_le_bytes(*b"01234567")), Some(1234567));
+/// assert_eq!(parse_digits8(u64::from_le_bytes(*b"0123 567")), None);
+/// ```
+#[inline]
+pub const fn parse_digits8(digits: u64) -> Option<u32> {
+    if Bits8(digits as u128).ascii_digit_lane_mask().0 as u64 != SIGNS8 as u64 {
+        return None;
+    }
+    Some(combine_digits(digits as u128).0 as u32)
+}
+
+/// Parses the 16 ASCII digits in `digits`, which has the first digit in the lowest
+/// byte, the same as `u128::from_le_bytes`.
+///
+/// Returns `None` unless every byte is a digit.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(
+///     parse_digits16(u128::from_le_bytes(*b"9876543210123456")),
+///     Some(9876543210123456)
+/// );
+/// assert_eq!(parse_digits16(u128::from_le_bytes(*b"98765432101234-6")), None);
+/// ```
+#[inline]
+pub const fn parse_digits16(digits: u128) -> Option<u64> {
+    if Bits8(digits).ascii_digit_lane_mask().0 != SIGNS8 {
+        return None;
+    }
+    let (ones, hundred_millions) = combine_digits(digits).split();
+    Some((hundred_millions.0 * 100_000_000 + ones.0) as u64)
+}
+
+/// Parses a decimal number the same way as `u64::from_str`, 16 digits at a time.
+///
+/// The number may start with a `+`, and `None` is returned if it is empty, has
+/// anything other than digits, or doesn't fit in a `u64`.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(parse_u64(b"18446744073709551615"), Some(u64::MAX));
+/// assert_eq!(parse_u64(b"+0042"), Some(42));
+/// assert_eq!(parse_u64(b"18446744073709551616"), None);
+/// assert_eq!(parse_u64(b"4.2"), None);
+/// ```
+pub fn parse_u64(bytes: &[u8]) -> Option<u64> {
+    let digits = match bytes {
+        [b'+', digits @ ..] => digits,
+        digits => digits,
+    };
+    if digits.is_empty() {
+        return None;
+    }
+    // The first chunk is short so that the rest have 16 digits each. It is padded
+    // with leading zeros.
+    let first_len = (digits.len() - 1) % 16 + 1;
+    let mut word = [b'0'; 16];
+    word[16 - first_len..].copy_from_slice(&digits[..first_len]);
+    let mut value = parse_digits16(u128::from_le_bytes(word))?;
+    for chunk in digits[first_len..].chunks(16) {
+        word.copy_from_slice(chunk);
+        value = value
+            .checked_mul(10_000_000_000_000_000)?
+            .checked_add(parse_digits16(u128::from_le_bytes(word))?)?;
+    }
+    Some(value)
+}
@@ -0,0 +1,167 @@
+use core::fmt;
+
+/// A value that is too large for an element of the given width.
+///
+/// ```
+/// use swar::*;
+///
+/// let error = Bits4::try_from_element(0x1F).unwrap_err();
+/// assert_eq!(error.to_string(), "31 does not fit in a 4-bit element");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ElementOutOfRange {
+    /// The width of the element in bits.
+    pub width: u32,
+    /// The value that didn't fit.
+    pub value: u128,
+}
+
+impl fmt::Display for ElementOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} does not fit in a {}-bit element",
+            self.value, self.width
+        )
+    }
+}
+
+impl core::error::Error for ElementOutOfRange {}
+
+/// A byte that isn't a hexadecimal digit.
+///
+/// ```
+/// use swar::*;
+///
+/// let error = hex_decode(*b"00000000deadbeeg").unwrap_err();
+/// assert_eq!(error, InvalidHexDigit { index: 15, byte: b'g' });
+/// assert_eq!(error.to_string(), "invalid hex digit 0x67 at index 15");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidHexDigit {
+    /// The index of the byte in the input.
+    pub index: usize,
+    /// The byte that isn't a digit.
+    pub byte: u8,
+}
+
+impl fmt::Display for InvalidHexDigit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid hex digit {:#04x} at index {}",
+            self.byte, self.index
+        )
+    }
+}
+
+impl core::error::Error for InvalidHexDigit {}
+
+/// A byte that isn't in the base64 alphabet, or a last byte that can't end the input.
+///
+/// ```
+/// use swar::*;
+///
+/// let error = Base64::UrlSafe.decode_chunk(*b"3q2+7w==").unwrap_err();
+/// assert_eq!(error, InvalidBase64 { index: 3, byte: b'+' });
+/// assert_eq!(error.to_string(), "invalid base64 byte 0x2b at index 3");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidBase64 {
+    /// The index of the byte in the input.
+    pub index: usize,
+    /// The byte that isn't valid there.
+    pub byte: u8,
+}
+
+impl fmt::Display for InvalidBase64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid base64 byte {:#04x} at index {}",
+            self.byte, self.index
+        )
+    }
+}
+
+impl core::error::Error for InvalidBase64 {}
+
+/// A LEB128 integer that ends early or doesn't fit in a `u64`.
+///
+/// ```
+/// use swar::*;
+///
+/// let error = decode_leb128(b"\x80\x80").unwrap_err();
+/// assert_eq!(error, InvalidLeb128 { index: 0, truncated: true });
+/// assert_eq!(error.to_string(), "truncated LEB128 integer at index 0");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidLeb128 {
+    /// The index of the first byte of the integer.
+    pub index: usize,
+    /// Whether the bytes ended before the last byte of the integer, rather than
+    /// the integer being too large.
+    pub truncated: bool,
+}
+
+impl fmt::Display for InvalidLeb128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.truncated {
+            write!(f, "truncated LEB128 integer at index {}", self.index)
+        } else {
+            write!(f, "LEB128 integer at index {} overflows u64", self.index)
+        }
+    }
+}
+
+impl core::error::Error for InvalidLeb128 {}
+
+/// Bytes that are not valid UTF-8, with the same positions as `core::str::Utf8Error`.
+///
+/// ```
+/// use swar::*;
+///
+/// let error = validate_utf8(b"caf\xC3\xA9 \xE2\x82").unwrap_err();
+/// assert_eq!(error.valid_up_to(), 6);
+/// assert_eq!(error.error_len(), None);
+/// assert_eq!(error.to_string(), core::str::from_utf8(b"caf\xC3\xA9 \xE2\x82").unwrap_err().to_string());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Utf8Error {
+    pub(crate) valid_up_to: usize,
+    pub(crate) error_len: Option<u8>,
+}
+
+impl Utf8Error {
+    /// The index up to which the bytes are valid UTF-8.
+    #[inline]
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// The length of the invalid sequence at `valid_up_to`, or `None` if the
+    /// bytes end in the middle of a sequence that could still be valid.
+    #[inline]
+    pub fn error_len(&self) -> Option<usize> {
+        self.error_len.map(|len| len as usize)
+    }
+}
+
+impl fmt::Display for Utf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error_len {
+            Some(len) => write!(
+                f,
+                "invalid utf-8 sequence of {} bytes from index {}",
+                len, self.valid_up_to
+            ),
+            None => write!(
+                f,
+                "incomplete utf-8 byte sequence from index {}",
+                self.valid_up_to
+            ),
+        }
+    }
+}
+
+impl core::error::Error for Utf8Error {}
@@ -0,0 +1,121 @@
+use crate::u128::SIGNS8;
+use crate::*;
+
+/// Writes `n` as 16 lowercase hexadecimal digits, most significant first.
+///
+/// Each nibble is spread into its own byte with `halve`, and then the lanes past
+/// 9 get the offset of `'a'` instead of `'0'`.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(&hex_encode(0x0123_4567_89AB_CDEF), b"0123456789abcdef");
+/// ```
+#[inline]
+pub const fn hex_encode(n: u64) -> [u8; 16] {
+    let (_, nibbles) = Bits4(n as u128).halve();
+    let letters = nibbles.lanes_gt(Bits8::from_element(9));
+    let digits =
+        nibbles.0 + Bits8::from_element(b'0' as u128).0 + letters.0 * (b'a' - b'0' - 10) as u128;
+    digits.to_be_bytes()
+}
+
+/// Reads 16 hexadecimal digits, most significant first, in either case.
+///
+/// Each byte is turned into its nibble in place, and then the nibbles are packed
+/// together with `squash`.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(hex_decode(*b"0123456789ABCDEF"), Ok(0x0123_4567_89AB_CDEF));
+/// assert_eq!(
+///     hex_decode(*b"0123456789ABCDEx"),
+///     Err(InvalidHexDigit { index: 15, byte: b'x' })
+/// );
+/// ```
+#[inline]
+pub const fn hex_decode(hex: [u8; 16]) -> Result<u64, InvalidHexDigit> {
+    // The last digit goes in the first lane, so that the lanes line up with the
+    // nibbles of the result.
+    let word = Bits8(u128::from_be_bytes(hex));
+    let digits = word.ascii_digit_lane_mask();
+    let letters = Bits8(word.0 | Bits8::from_element(0x20).0).ascii_range_lane_mask(b'a', b'f');
+    let invalid = !(digits.0 | letters.0) & SIGNS8;
+    if invalid != 0 {
+        let index = invalid.leading_zeros() as usize / 8;
+        return Err(InvalidHexDigit {
+            index,
+            byte: hex[index],
+        });
+    }
+    let nibbles = (word.0 & Bits8::from_element(0x0F).0) + (letters.0 >> 7) * 9;
+    Ok(Bits8(nibbles).squash().0 as u64)
+}
+
+/// Writes every byte of `bytes` as two lowercase hexadecimal digits into `hex`,
+/// 8 bytes at a time.
+///
+/// # Panics
+///
+/// Panics if `hex` isn't twice as long as `bytes`.
+///
+/// ```
+/// use swar::*;
+///
+/// let mut hex = [0; 20];
+/// hex_encode_slice(b"\x00\x01\x7F\x80\xFF\xDE\xAD\xBE\xEF\x0A", &mut hex);
+/// assert_eq!(&hex, b"00017f80ffdeadbeef0a");
+/// ```
+pub fn hex_encode_slice(bytes: &[u8], hex: &mut [u8]) {
+    assert_eq!(
+        hex.len(),
+        2 * bytes.len(),
+        "hex must be twice as long as bytes"
+    );
+    for (chunk, hex) in bytes.chunks(8).zip(hex.chunks_mut(16)) {
+        let mut word = [0; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        hex.copy_from_slice(&hex_encode(u64::from_be_bytes(word))[..hex.len()]);
+    }
+}
+
+/// Reads every two hexadecimal digits of `hex` into a byte of `bytes`, 16 digits
+/// at a time.
+///
+/// Returns the first byte of `hex` that isn't a digit, in which case `bytes` may
+/// have been partly written.
+///
+/// # Panics
+///
+/// Panics if `hex` isn't twice as long as `bytes`.
+///
+/// ```
+/// use swar::*;
+///
+/// let mut bytes = [0; 10];
+/// hex_decode_slice(b"00017f80ffDEADBEEF0a", &mut bytes).unwrap();
+/// assert_eq!(&bytes, b"\x00\x01\x7F\x80\xFF\xDE\xAD\xBE\xEF\x0A");
+/// assert_eq!(
+///     hex_decode_slice(b"00017f80ffDEADBEEF0-", &mut bytes),
+///     Err(InvalidHexDigit { index: 19, byte: b'-' })
+/// );
+/// ```
+pub fn hex_decode_slice(hex: &[u8], bytes: &mut [u8]) -> Result<(), InvalidHexDigit> {
+    assert_eq!(
+        hex.len(),
+        2 * bytes.len(),
+        "hex must be twice as long as bytes"
+    );
+    for (i, (chunk, bytes)) in hex.chunks(16).zip(bytes.chunks_mut(8)).enumerate() {
+        // Pad with zero digits, which only fill the bytes that are dropped.
+        let mut word = [b'0'; 16];
+        word[..chunk.len()].copy_from_slice(chunk);
+        let n = hex_decode(word).map_err(|error| InvalidHexDigit {
+            index: 16 * i + error.index,
+            ..error
+        })?;
+        bytes.copy_from_slice(&n.to_be_bytes()[..bytes.len()]);
+    }
+    Ok(())
+}
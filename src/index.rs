@@ -0,0 +1,159 @@
+//! A hamming weight tree for hamming radius search over `u128` codes.
+
+use crate::*;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// The number of levels of the tree, from the `bits64` weights down to the codes themselves.
+const LEVELS: usize = 7;
+
+/// A tree that buckets codes by successive `sum_weight2` levels of their hamming weights.
+///
+/// The children of the root are keyed by the weights of the two halves of each code
+/// (`bits64`), their children by the weights of the four quarters (`bits32`), and so on
+/// down to the leaves, which are keyed by the codes themselves (`bits1`). Because every
+/// code under a node shares its weights, the sum of `minhwd` between the query weights
+/// and the node key is a lower bound on the distance to every code beneath it, so whole
+/// subtrees are skipped during a search once the bound exceeds the radius.
+///
+/// ```
+/// use swar::index::HammingWeightTree;
+/// use swar::*;
+///
+/// let mut tree = HammingWeightTree::new();
+/// for &code in &[0u128, 0b1, 0b11 << 64, !0] {
+///     tree.insert(code);
+/// }
+/// let found = tree.search(0b10, 2);
+/// assert_eq!(found, [Neighbor { distance: 1, index: 0 }, Neighbor { distance: 2, index: 1 }]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HammingWeightTree {
+    root: Node,
+    codes: Vec<u128>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    children: BTreeMap<u128, Node>,
+    /// The indices of the codes at this node, which is only non-empty for leaves.
+    ids: Vec<usize>,
+}
+
+impl HammingWeightTree {
+    /// Creates an empty tree.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of codes in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// The codes in the order they were inserted.
+    #[inline]
+    pub fn codes(&self) -> &[u128] {
+        &self.codes
+    }
+
+    /// Inserts a code and returns its index.
+    pub fn insert(&mut self, code: u128) -> usize {
+        let index = self.codes.len();
+        self.codes.push(code);
+        let pyramid = WeightPyramid::new(code);
+        let mut node = &mut self.root;
+        for level in 0..LEVELS {
+            node = node.children.entry(key(level, &pyramid)).or_default();
+        }
+        node.ids.push(index);
+        index
+    }
+
+    /// Finds every code within hamming distance `radius` of `query`.
+    ///
+    /// The neighbors are returned from best to worst.
+    pub fn search(&self, query: u128, radius: u32) -> Vec<Neighbor> {
+        let query = WeightPyramid::new(query);
+        let mut found = Vec::new();
+        search(&self.root, 0, &query, radius, &mut found);
+        found.sort_unstable();
+        found
+    }
+}
+
+impl Extend<u128> for HammingWeightTree {
+    fn extend<I: IntoIterator<Item = u128>>(&mut self, codes: I) {
+        for code in codes {
+            self.insert(code);
+        }
+    }
+}
+
+impl core::iter::FromIterator<u128> for HammingWeightTree {
+    fn from_iter<I: IntoIterator<Item = u128>>(codes: I) -> Self {
+        let mut tree = Self::new();
+        tree.extend(codes);
+        tree
+    }
+}
+
+fn search(
+    node: &Node,
+    level: usize,
+    query: &WeightPyramid,
+    radius: u32,
+    found: &mut Vec<Neighbor>,
+) {
+    for (&child_key, child) in &node.children {
+        let bound = bound(level, query, child_key);
+        if bound > radius {
+            continue;
+        }
+        if level + 1 == LEVELS {
+            // The last level is keyed by the code, so its bound is the exact distance.
+            found.extend(child.ids.iter().map(|&index| Neighbor {
+                distance: bound,
+                index,
+            }));
+        } else {
+            search(child, level + 1, query, radius, found);
+        }
+    }
+}
+
+/// Gets the key of a code at a level of the tree.
+#[inline]
+fn key(level: usize, pyramid: &WeightPyramid) -> u128 {
+    match level {
+        0 => pyramid.bits64.0,
+        1 => pyramid.bits32.0,
+        2 => pyramid.bits16.0,
+        3 => pyramid.bits8.0,
+        4 => pyramid.bits4.0,
+        5 => pyramid.bits2.0,
+        _ => pyramid.bits1.0,
+    }
+}
+
+/// Computes the lower bound on the distance from the query to every code under a key.
+#[inline]
+fn bound(level: usize, query: &WeightPyramid, key: u128) -> u32 {
+    let bound = match level {
+        0 => query.bits64.minhwd(Bits64(key)).sum_weight(),
+        1 => query.bits32.minhwd(Bits32(key)).sum_weight(),
+        2 => query.bits16.minhwd(Bits16(key)).sum_weight(),
+        3 => query.bits8.minhwd(Bits8(key)).sum_weight(),
+        4 => query.bits4.minhwd(Bits4(key)).sum_weight(),
+        5 => query.bits2.minhwd(Bits2(key)).sum_weight(),
+        _ => query.bits1.minhwd(Bits1(key)).sum_weight(),
+    };
+    bound as u32
+}
@@ -0,0 +1,206 @@
+use crate::*;
+
+/// A descriptor found by a k-nearest-neighbor search.
+///
+/// Neighbors are ordered by distance first and then by index, so the
+/// earliest descriptor wins ties.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Neighbor {
+    /// The hamming distance from the query.
+    pub distance: u32,
+    /// The index of the descriptor in the searched slice.
+    pub index: usize,
+}
+
+/// A fixed-size max-heap that keeps the best `k` neighbors in a caller-provided buffer.
+///
+/// The size of the buffer is `k`. The worst neighbor kept is always at the root,
+/// so it can be used as a running bound to prune candidates.
+///
+/// ```
+/// use swar::*;
+///
+/// let mut buffer = [Neighbor::default(); 2];
+/// let mut heap = NeighborHeap::new(&mut buffer);
+/// heap.push(Neighbor { distance: 5, index: 0 });
+/// heap.push(Neighbor { distance: 3, index: 1 });
+/// heap.push(Neighbor { distance: 4, index: 2 });
+/// assert_eq!(heap.bound(), 4);
+/// let best = heap.into_sorted();
+/// assert_eq!(best, [Neighbor { distance: 3, index: 1 }, Neighbor { distance: 4, index: 2 }]);
+/// ```
+#[derive(Debug)]
+pub struct NeighborHeap<'a> {
+    heap: &'a mut [Neighbor],
+    len: usize,
+}
+
+impl<'a> NeighborHeap<'a> {
+    /// Creates an empty heap that keeps `buffer.len()` neighbors.
+    #[inline]
+    pub fn new(buffer: &'a mut [Neighbor]) -> Self {
+        Self {
+            heap: buffer,
+            len: 0,
+        }
+    }
+
+    /// The number of neighbors currently kept.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` once `k` neighbors are kept.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == self.heap.len()
+    }
+
+    /// A candidate must have a distance below this to be kept.
+    ///
+    /// This is `u32::MAX` until the heap is full.
+    #[inline]
+    pub fn bound(&self) -> u32 {
+        if self.is_full() {
+            self.heap.first().map_or(0, |worst| worst.distance)
+        } else {
+            u32::MAX
+        }
+    }
+
+    /// Adds a neighbor, evicting the worst one if the heap is full.
+    ///
+    /// Returns `false` if the neighbor was not kept.
+    #[inline]
+    pub fn push(&mut self, neighbor: Neighbor) -> bool {
+        if !self.is_full() {
+            self.heap[self.len] = neighbor;
+            self.len += 1;
+            self.sift_up(self.len - 1);
+            true
+        } else if self.heap.first().is_some_and(|&worst| neighbor < worst) {
+            self.heap[0] = neighbor;
+            self.sift_down(0, self.len);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Sorts the kept neighbors from best to worst and returns them.
+    pub fn into_sorted(self) -> &'a mut [Neighbor] {
+        let Self { heap, len } = self;
+        let heap = &mut heap[..len];
+        let mut sorter = NeighborHeap { heap, len };
+        for end in (1..len).rev() {
+            sorter.heap.swap(0, end);
+            sorter.sift_down(0, end);
+        }
+        sorter.heap
+    }
+
+    fn sift_up(&mut self, mut child: usize) {
+        while child > 0 {
+            let parent = (child - 1) / 2;
+            if self.heap[child] <= self.heap[parent] {
+                break;
+            }
+            self.heap.swap(child, parent);
+            child = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut parent: usize, end: usize) {
+        loop {
+            let left = 2 * parent + 1;
+            if left >= end {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < end && self.heap[right] > self.heap[left] {
+                right
+            } else {
+                left
+            };
+            if self.heap[child] <= self.heap[parent] {
+                break;
+            }
+            self.heap.swap(child, parent);
+            parent = child;
+        }
+    }
+}
+
+/// Computes the hamming distance word by word, giving up once it reaches `bound`.
+#[inline]
+pub(crate) fn distance_below<const K: usize>(
+    a: &[u128; K],
+    b: &[u128; K],
+    bound: u32,
+) -> Option<u32> {
+    let mut distance = 0;
+    for (&a, &b) in a.iter().zip(b) {
+        distance += Bits1(a).minhwd(Bits1(b)).count_ones();
+        if distance >= bound {
+            return None;
+        }
+    }
+    Some(distance)
+}
+
+/// Finds the `buffer.len()` nearest descriptors to `query` by brute force.
+///
+/// The distance of each candidate is abandoned as soon as it cannot beat the
+/// current kth-best distance. The neighbors are returned from best to worst
+/// and there are fewer than `k` of them only if there are fewer descriptors.
+///
+/// ```
+/// use swar::*;
+///
+/// let descriptors = [[0b1111, 0], [0b0001, 0], [0, 0b11], [0, 0]];
+/// let mut buffer = [Neighbor::default(); 2];
+/// let best = knn(&[0, 0b1], &descriptors, &mut buffer);
+/// assert_eq!(best, [Neighbor { distance: 1, index: 2 }, Neighbor { distance: 1, index: 3 }]);
+/// ```
+pub fn knn<'a, const K: usize>(
+    query: &[u128; K],
+    descriptors: &[[u128; K]],
+    buffer: &'a mut [Neighbor],
+) -> &'a mut [Neighbor] {
+    let mut heap = NeighborHeap::new(buffer);
+    for (index, descriptor) in descriptors.iter().enumerate() {
+        if let Some(distance) = distance_below(query, descriptor, heap.bound()) {
+            heap.push(Neighbor { distance, index });
+        }
+    }
+    heap.into_sorted()
+}
+
+/// Finds the `k` nearest descriptors to `query` by brute force.
+///
+/// This is the same as `knn`, but allocates the buffer.
+///
+/// ```
+/// use swar::*;
+///
+/// let descriptors = [[0b1111], [0b0001], [0b0111]];
+/// let best = knn_vec(&[0b0011], &descriptors, 2);
+/// assert_eq!(best, [Neighbor { distance: 1, index: 1 }, Neighbor { distance: 1, index: 2 }]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn knn_vec<const K: usize>(
+    query: &[u128; K],
+    descriptors: &[[u128; K]],
+    k: usize,
+) -> alloc::vec::Vec<Neighbor> {
+    let mut buffer = alloc::vec![Neighbor::default(); k];
+    let len = knn(query, descriptors, &mut buffer).len();
+    buffer.truncate(len);
+    buffer
+}
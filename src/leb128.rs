@@ -0,0 +1,116 @@
+use crate::bytes::load;
+use crate::u128::{ones, SIGNS8};
+use crate::*;
+
+/// The most bytes a LEB128 `u64` can take, since 10 bytes hold 70 bits.
+const MAX_LEN: usize = 10;
+
+/// Packs the 7-bit payloads of the first `len` bytes of `word` together, returning
+/// `None` if they don't fit in a `u64`.
+#[inline]
+const fn payload(word: u128, len: usize) -> Option<u64> {
+    // The tenth byte only has room for bit 63.
+    if len > MAX_LEN || len == MAX_LEN && word >> 72 & 0x7F > 1 {
+        return None;
+    }
+    let mut x = word & (u128::MAX >> (128 - 8 * len)) & !SIGNS8;
+    // Every round moves the fields in the upper half of each pair of lanes down
+    // against the fields in the lower half, like `pext` with the payload bits.
+    let mut lane = 8;
+    let mut field = 7;
+    while lane < 128 {
+        let low = ones(2 * lane) * (u128::MAX >> (128 - field));
+        x = x & low | (x & low << lane) >> (lane - field);
+        lane *= 2;
+        field *= 2;
+    }
+    Some(x as u64)
+}
+
+/// Decodes the unsigned LEB128 integer at the start of `bytes`, returning it
+/// along with the number of bytes it took.
+///
+/// The bytes are loaded 16 at a time, and the last byte of the integer is the
+/// first one without its sign bit set.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(decode_leb128(b"\x7F"), Ok((127, 1)));
+/// assert_eq!(decode_leb128(b"\xE5\x8E\x26\xFF"), Ok((624485, 3)));
+/// assert_eq!(
+///     decode_leb128(b"\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x02"),
+///     Err(InvalidLeb128 { index: 0, truncated: false })
+/// );
+/// ```
+pub fn decode_leb128(bytes: &[u8]) -> Result<(u64, usize), InvalidLeb128> {
+    let (Bits(word), valid) = load(bytes, 0);
+    let overflow = InvalidLeb128 {
+        index: 0,
+        truncated: false,
+    };
+    match Bits8(!word & valid & SIGNS8).first_match_index() {
+        Some(last) => {
+            let len = last as usize + 1;
+            payload(word, len).map(|n| (n, len)).ok_or(overflow)
+        }
+        None if bytes.len() >= MAX_LEN => Err(overflow),
+        None => Err(InvalidLeb128 {
+            index: 0,
+            truncated: true,
+        }),
+    }
+}
+
+/// Decodes unsigned LEB128 integers from `bytes` into `values` until either one
+/// runs out, returning how many values were written and how many bytes they took.
+///
+/// Every integer that ends in the 16 bytes loaded at a time is decoded before
+/// the next load.
+///
+/// ```
+/// use swar::*;
+///
+/// let mut values = [0; 4];
+/// assert_eq!(
+///     decode_leb128_slice(b"\x01\xAC\x02\xE5\x8E\x26", &mut values),
+///     Ok((3, 6))
+/// );
+/// assert_eq!(values, [1, 300, 624485, 0]);
+/// assert_eq!(
+///     decode_leb128_slice(b"\x01\xAC\x02\xE5\x8E", &mut values),
+///     Err(InvalidLeb128 { index: 3, truncated: true })
+/// );
+/// ```
+pub fn decode_leb128_slice(
+    bytes: &[u8],
+    values: &mut [u64],
+) -> Result<(usize, usize), InvalidLeb128> {
+    let mut read = 0;
+    let mut written = 0;
+    while written < values.len() && read < bytes.len() {
+        let (Bits(word), valid) = load(bytes, read);
+        let mut ends = !word & valid & SIGNS8;
+        if ends == 0 {
+            // Nothing ends in the next 16 bytes, which is too long for a `u64`
+            // unless the bytes ran out first.
+            return Err(InvalidLeb128 {
+                index: read,
+                truncated: bytes.len() - read < MAX_LEN,
+            });
+        }
+        let mut start = 0;
+        while ends != 0 && written < values.len() {
+            let end = Bits8(ends).first_match_index().unwrap() as usize + 1;
+            values[written] = payload(word >> (8 * start), end - start).ok_or(InvalidLeb128 {
+                index: read + start,
+                truncated: false,
+            })?;
+            written += 1;
+            start = end;
+            ends &= ends - 1;
+        }
+        read += start;
+    }
+    Ok((written, read))
+}
@@ -33,38 +33,221 @@
 //! assert_eq!(a.minhwd(b), expected);
 //! ```
 #![no_std]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 #![allow(clippy::many_single_char_names)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod ascii;
+mod base64;
+mod bcd;
+mod bytes;
+mod decimal;
+mod error;
+mod hex;
+#[cfg(feature = "index")]
+pub mod index;
+mod knn;
+mod leb128;
+#[cfg(feature = "alloc")]
+mod mih;
+mod padded;
+mod pyramid;
+mod sad;
 pub mod u128;
+pub mod u64;
+mod utf8;
+
+pub use ascii::*;
+pub use base64::*;
+pub use bcd::*;
+pub use bytes::*;
+pub use decimal::*;
+pub use error::*;
+pub use hex::*;
+pub use knn::*;
+pub use leb128::*;
+#[cfg(feature = "alloc")]
+pub use mih::*;
+pub use padded::*;
+pub use pyramid::*;
+pub use sad::*;
+pub use utf8::*;
+
+use core::fmt;
+
+/// A number whose bits are split into elements of `WIDTH` bits stored in parallel.
+///
+/// `WIDTH` can be anything from `1` to `128`. The routines which keep the width
+/// of the elements, such as `minhwd` or `any`, are written once for every width.
+/// Routines which change the width, such as `sum_weight2` or `split`, are implemented
+/// for each power-of-two width because `Bits<{ 2 * WIDTH }, N>` can't be named on stable yet.
+///
+/// When `WIDTH` doesn't divide the number, as with 10-bit samples or 21-bit code points,
+/// the bits above the last whole element are unused. The routines on hamming weights
+/// only compile for power-of-two widths.
+///
+/// ```compile_fail
+/// use swar::*;
+///
+/// let _ = Bits10(1).minhwd(Bits10(2));
+/// ```
+///
+/// The aliases `Bits1` through `Bits128` name each width, and the functions of the
+/// same name construct them, so `Bits8(n)` is the same as `Bits::<8, _>(n)`. Patterns
+/// must use the struct itself, as in `let Bits(n) = Bits8(n)`.
+///
+/// ```
+/// use swar::*;
+///
+/// let Bits(n) = Bits4::from_element(3);
+/// assert_eq!(n, 0x3333_3333_3333_3333_3333_3333_3333_3333);
+/// assert_eq!(Bits8(n).any(), Bits::<8, u128>::from_element(1));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Bits<const WIDTH: u32, N>(pub N);
+
+impl<const WIDTH: u32, N: fmt::Debug> fmt::Debug for Bits<WIDTH, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Print the width as part of the name to match the aliases.
+        write!(f, "Bits{}(", WIDTH)?;
+        self.0.fmt(f)?;
+        f.write_str(")")
+    }
+}
 
 /// This is used when each bit is a number stored in parallel.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct Bits1<N>(pub N);
+pub type Bits1<N = u128> = Bits<1, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits1<N>(n: N) -> Bits1<N> {
+    Bits(n)
+}
 
 /// This is used when every `2` bits is a number stored in parallel.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct Bits2<N>(pub N);
+pub type Bits2<N = u128> = Bits<2, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits2<N>(n: N) -> Bits2<N> {
+    Bits(n)
+}
 
 /// This is used when every `4` bits is a number stored in parallel.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct Bits4<N>(pub N);
+pub type Bits4<N = u128> = Bits<4, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits4<N>(n: N) -> Bits4<N> {
+    Bits(n)
+}
 
 /// This is used when every `8` bits is a number stored in parallel.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct Bits8<N>(pub N);
+pub type Bits8<N = u128> = Bits<8, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits8<N>(n: N) -> Bits8<N> {
+    Bits(n)
+}
 
 /// This is used when every `16` bits is a number stored in parallel.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct Bits16<N>(pub N);
+pub type Bits16<N = u128> = Bits<16, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits16<N>(n: N) -> Bits16<N> {
+    Bits(n)
+}
 
 /// This is used when every `32` bits is a number stored in parallel.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct Bits32<N>(pub N);
+pub type Bits32<N = u128> = Bits<32, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits32<N>(n: N) -> Bits32<N> {
+    Bits(n)
+}
 
 /// This is used when every `64` bits is a number stored in parallel.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct Bits64<N>(pub N);
+pub type Bits64<N = u128> = Bits<64, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits64<N>(n: N) -> Bits64<N> {
+    Bits(n)
+}
 
 /// This is used when every `128` bits is a number stored in parallel.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct Bits128<N>(pub N);
+pub type Bits128<N = u128> = Bits<128, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits128<N>(n: N) -> Bits128<N> {
+    Bits(n)
+}
+
+/// This is used when every `3` bits is a number stored in parallel.
+pub type Bits3<N = u128> = Bits<3, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits3<N>(n: N) -> Bits3<N> {
+    Bits(n)
+}
+
+/// This is used when every `5` bits is a number stored in parallel.
+pub type Bits5<N = u128> = Bits<5, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits5<N>(n: N) -> Bits5<N> {
+    Bits(n)
+}
+
+/// This is used when every `6` bits is a number stored in parallel.
+pub type Bits6<N = u128> = Bits<6, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits6<N>(n: N) -> Bits6<N> {
+    Bits(n)
+}
+
+/// This is used when every `7` bits is a number stored in parallel.
+pub type Bits7<N = u128> = Bits<7, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits7<N>(n: N) -> Bits7<N> {
+    Bits(n)
+}
+
+/// This is used when every `10` bits is a number stored in parallel.
+pub type Bits10<N = u128> = Bits<10, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits10<N>(n: N) -> Bits10<N> {
+    Bits(n)
+}
+
+/// This is used when every `12` bits is a number stored in parallel.
+pub type Bits12<N = u128> = Bits<12, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits12<N>(n: N) -> Bits12<N> {
+    Bits(n)
+}
+
+/// This is used when every `21` bits is a number stored in parallel.
+pub type Bits21<N = u128> = Bits<21, N>;
+
+#[allow(non_snake_case)]
+#[inline]
+pub const fn Bits21<N>(n: N) -> Bits21<N> {
+    Bits(n)
+}
@@ -0,0 +1,159 @@
+use crate::knn::distance_below;
+use crate::*;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// The number of bits in each substring, which is one `Bits16` element.
+const SUBSTRING_BITS: u32 = 16;
+/// The number of substrings in each `u128` word of a code.
+const SUBSTRINGS: usize = 128 / SUBSTRING_BITS as usize;
+/// The number of buckets in the table of each substring.
+const BUCKETS: usize = 1 << SUBSTRING_BITS;
+
+/// A multi-index hashing index for sub-linear hamming radius search.
+///
+/// See Norouzi et al., "Fast Search in Hamming Space with Multi-Index Hashing".
+///
+/// Each code is split into `m` substrings, one for every `Bits16` element,
+/// so `m` is `8` for 128-bit codes and `16` for 256-bit codes. Every substring
+/// gets its own table which maps all `2^16` values to the codes that contain it.
+/// If two codes are within distance `r`, then at least one of their substrings
+/// must be within distance `r / m`, so only the buckets within that radius
+/// of the query substrings need to be visited. The candidates are then verified
+/// with their full hamming distance.
+///
+/// ```
+/// use swar::*;
+///
+/// let index = MultiIndex::new(vec![[0u128; 2], [0b111, 0], [0, 1 << 100], [!0, !0]]);
+/// let found = index.search(&[0b1, 0], 2);
+/// assert_eq!(found, [Neighbor { distance: 1, index: 0 }, Neighbor { distance: 2, index: 1 }, Neighbor { distance: 2, index: 2 }]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MultiIndex<const K: usize> {
+    codes: Vec<[u128; K]>,
+    /// The start of every bucket in `ids` for every substring, followed by the end of the last one.
+    offsets: Vec<u32>,
+    ids: Vec<u32>,
+}
+
+impl<const K: usize> MultiIndex<K> {
+    /// Builds the index over `codes`.
+    ///
+    /// There can be no more than `u32::MAX / (8 * K)` codes.
+    pub fn new(codes: Vec<[u128; K]>) -> Self {
+        let substrings = SUBSTRINGS * K;
+        let len = codes.len();
+        assert!(
+            len.checked_mul(substrings)
+                .is_some_and(|ids| ids <= u32::MAX as usize),
+            "too many codes for a multi-index"
+        );
+        // Count the codes in every bucket, offset by one so that the prefix sum
+        // gives the start of each bucket.
+        let mut offsets = vec![0u32; substrings * (BUCKETS + 1)];
+        for code in &codes {
+            for substring in 0..substrings {
+                offsets[table(substring) + key(substring, code) + 1] += 1;
+            }
+        }
+        for substring in 0..substrings {
+            let table = &mut offsets[table(substring)..table(substring + 1)];
+            table[0] = (substring * len) as u32;
+            for i in 1..table.len() {
+                table[i] += table[i - 1];
+            }
+        }
+        let mut cursors = offsets.clone();
+        let mut ids = vec![0u32; substrings * len];
+        for (id, code) in codes.iter().enumerate() {
+            for substring in 0..substrings {
+                let cursor = &mut cursors[table(substring) + key(substring, code)];
+                ids[*cursor as usize] = id as u32;
+                *cursor += 1;
+            }
+        }
+        Self {
+            codes,
+            offsets,
+            ids,
+        }
+    }
+
+    /// The number of codes in the index.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// The codes in the order they were given to `new`.
+    #[inline]
+    pub fn codes(&self) -> &[[u128; K]] {
+        &self.codes
+    }
+
+    /// Finds every code within hamming distance `radius` of `query`.
+    ///
+    /// The neighbors are returned from best to worst.
+    pub fn search(&self, query: &[u128; K], radius: u32) -> Vec<Neighbor> {
+        let substrings = SUBSTRINGS * K;
+        let substring_radius = radius / substrings as u32;
+        let mut candidates = Vec::new();
+        for substring in 0..substrings {
+            let table = table(substring);
+            let key = key(substring, query);
+            for_each_flip(substring_radius, |flip| {
+                let bucket = table + (key ^ flip as usize);
+                let ids =
+                    &self.ids[self.offsets[bucket] as usize..self.offsets[bucket + 1] as usize];
+                candidates.extend_from_slice(ids);
+            });
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+        let mut found = candidates
+            .into_iter()
+            .filter_map(|id| {
+                let index = id as usize;
+                distance_below(query, &self.codes[index], radius.saturating_add(1))
+                    .map(|distance| Neighbor { distance, index })
+            })
+            .collect::<Vec<Neighbor>>();
+        found.sort_unstable();
+        found
+    }
+}
+
+/// Finds the start of the table of `substring` in `offsets`.
+#[inline]
+fn table(substring: usize) -> usize {
+    substring * (BUCKETS + 1)
+}
+
+/// Extracts the `Bits16` element of `code` used as the key for `substring`.
+#[inline]
+fn key<const K: usize>(substring: usize, code: &[u128; K]) -> usize {
+    let word = code[substring / SUBSTRINGS];
+    let shift = (substring % SUBSTRINGS) as u32 * SUBSTRING_BITS;
+    (word >> shift) as u16 as usize
+}
+
+/// Calls `f` with every substring of bit flips that has no more than `radius` bits set.
+#[inline]
+fn for_each_flip(radius: u32, mut f: impl FnMut(u16)) {
+    f(0);
+    for weight in 1..=core::cmp::min(radius, SUBSTRING_BITS) {
+        // Visit every number with `weight` bits set in increasing order (Gosper's hack).
+        let mut v: u32 = (1 << weight) - 1;
+        while v < 1 << SUBSTRING_BITS {
+            f(v as u16);
+            let t = v | (v - 1);
+            v = (t + 1) | (((!t & (t + 1)) - 1) >> (v.trailing_zeros() + 1));
+        }
+    }
+}
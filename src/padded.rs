@@ -0,0 +1,185 @@
+use crate::u128::ones;
+use crate::*;
+use core::marker::PhantomData;
+
+/// Elements of the lane type `L` stored with `GUARD` extra bits above each one.
+///
+/// This is the `0A0B + 0C0D` layout from the README: the guard bits are left clear
+/// in every value so that adding the values carries into them instead of into the
+/// next element. `Padded<Bits7, 1>` keeps 7-bit elements in 8-bit lanes, and
+/// `Padded<Bits1, 7>` keeps 0 or 1 in each byte, which is how byte counts are
+/// summed before they are widened.
+///
+/// Up to `CAPACITY` values can be summed with `accumulate` without overflow, and
+/// `normalize` then splits the sums into lanes twice as wide so that they can be
+/// added up further.
+///
+/// ```
+/// use swar::*;
+///
+/// let values = [
+///     Padded::<Bits7, 1>::new(0x7F_01_00),
+///     Padded::<Bits7, 1>::new(0x7F_7F_05),
+/// ];
+/// let sums = Padded::accumulate(values);
+/// assert_eq!(sums.sums(), 0xFE_80_05);
+/// assert_eq!(sums.normalize(), (Bits16(0x0080), Bits16(0x00FE_0005)));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Padded<L, const GUARD: u32> {
+    sums: u128,
+    lanes: PhantomData<L>,
+}
+
+impl<const WIDTH: u32, const GUARD: u32> Padded<Bits<WIDTH, u128>, GUARD> {
+    /// The number of bits in each lane, including the guard bits.
+    pub const PADDED_WIDTH: u32 = WIDTH + GUARD;
+
+    /// The number of whole lanes.
+    pub const LANES: u32 = 128 / (WIDTH + GUARD);
+
+    /// The number of values that can be summed without overflowing a lane.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// assert_eq!(Padded::<Bits7, 1>::CAPACITY, 2);
+    /// assert_eq!(Padded::<Bits1, 7>::CAPACITY, 255);
+    /// assert_eq!(Padded::<Bits6, 2>::CAPACITY, 4);
+    /// ```
+    pub const CAPACITY: u128 = (u128::MAX >> (128 - WIDTH - GUARD)) / (u128::MAX >> (128 - WIDTH));
+
+    /// The elements of every lane with the guard bits set.
+    const GUARDS: u128 = ones(WIDTH + GUARD) * ((u128::MAX >> (128 - GUARD)) << WIDTH);
+
+    /// The element bits of every lane, which leaves out the guard bits and any
+    /// bits above the last whole lane.
+    const ELEMENTS: u128 = ones(WIDTH + GUARD) * (u128::MAX >> (128 - WIDTH));
+
+    /// Takes the elements from the lanes of `n`, which must have the guard bits
+    /// and any bits above the last whole lane clear.
+    ///
+    /// Debug builds panic if any of those bits are set. Use `try_new` to check
+    /// them instead.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// assert_eq!(Padded::<Bits7, 1>::new(0x7F_00_7F).sums(), 0x7F_00_7F);
+    /// ```
+    #[inline]
+    pub const fn new(n: u128) -> Self {
+        debug_assert!(n & !Self::ELEMENTS == 0, "element does not fit");
+        Self {
+            sums: n & Self::ELEMENTS,
+            lanes: PhantomData,
+        }
+    }
+
+    /// Takes the elements from the lanes of `n`, or fails with the first lane
+    /// that has a bit set outside of its element.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// assert_eq!(Padded::<Bits7, 1>::try_new(0x7F_00_7F), Ok(Padded::new(0x7F_00_7F)));
+    ///
+    /// let error = Padded::<Bits7, 1>::try_new(0xFF_80_7F).unwrap_err();
+    /// assert_eq!((error.width, error.value), (7, 0x80));
+    /// ```
+    #[inline]
+    pub const fn try_new(n: u128) -> Result<Self, ElementOutOfRange> {
+        let outside = n & !Self::ELEMENTS;
+        if outside == 0 {
+            return Ok(Self {
+                sums: n,
+                lanes: PhantomData,
+            });
+        }
+        // The bits above the last whole lane are reported as one more lane.
+        let lane = outside.trailing_zeros() / Self::PADDED_WIDTH;
+        Err(ElementOutOfRange {
+            width: WIDTH,
+            value: n >> (lane * Self::PADDED_WIDTH) & (u128::MAX >> (128 - Self::PADDED_WIDTH)),
+        })
+    }
+
+    /// The lanes, where each one has an element or a sum of elements.
+    #[inline]
+    pub const fn sums(self) -> u128 {
+        self.sums
+    }
+
+    /// Returns `true` if any sum has reached the guard bits.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// let value = Padded::<Bits7, 1>::new(0x40);
+    /// assert!(!value.has_carries());
+    /// assert!(Padded::accumulate([value, value]).has_carries());
+    /// ```
+    #[inline]
+    pub const fn has_carries(self) -> bool {
+        self.sums & Self::GUARDS != 0
+    }
+
+    /// Adds up every lane of `N` values.
+    ///
+    /// The sums can't overflow because `N` may be no more than `CAPACITY`,
+    /// which is checked when this is compiled.
+    ///
+    /// ```compile_fail
+    /// use swar::*;
+    ///
+    /// let value = Padded::<Bits7, 1>::new(0x7F);
+    /// let sums = Padded::accumulate([value; 3]);
+    /// ```
+    #[inline]
+    pub const fn accumulate<const N: usize>(values: [Self; N]) -> Self {
+        const {
+            assert!(
+                N as u128 <= Self::CAPACITY,
+                "too many values to accumulate without overflow"
+            )
+        };
+        let mut sums = 0;
+        let mut i = 0;
+        while i < N {
+            sums += values[i].sums;
+            i += 1;
+        }
+        Self {
+            sums,
+            lanes: PhantomData,
+        }
+    }
+}
+
+/// Implements `normalize` for every element width `$width` with guard bits that
+/// fill up `$padded`-bit lanes.
+macro_rules! impl_normalize {
+    ($padded:literal, $wide:literal; $($width:literal)*) => {
+        $(
+            impl Padded<Bits<$width, u128>, { $padded - $width }> {
+                #[doc = concat!("Splits the sums into ", stringify!($wide), "-bit lanes, which folds the guard bits back into the elements.")]
+                ///
+                /// The left and right outputs hold every other lane, the same as `split`.
+                #[inline]
+                pub const fn normalize(self) -> (Bits<$wide, u128>, Bits<$wide, u128>) {
+                    Bits::<$padded, u128>(self.sums).split()
+                }
+            }
+        )*
+    };
+}
+
+impl_normalize!(2, 4; 1);
+impl_normalize!(4, 8; 1 2 3);
+impl_normalize!(8, 16; 1 2 3 4 5 6 7);
+impl_normalize!(16, 32; 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15);
+impl_normalize!(32, 64;
+    1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31);
+impl_normalize!(64, 128;
+    1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
+    33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63);
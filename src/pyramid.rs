@@ -0,0 +1,198 @@
+use crate::*;
+
+/// Every level of hamming weights produced by chaining `sum_weight2` on a `u128`.
+///
+/// Each level holds the hamming weights of twice as many bits per element as
+/// the level before it, ending with the hamming weight of the whole number.
+/// Keeping all of them around allows search trees to compute cheap lower and
+/// upper bounds on the hamming distance at every level.
+///
+/// ```
+/// use swar::*;
+///
+/// let n = 0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF;
+/// let pyramid = WeightPyramid::new(n);
+/// assert_eq!(pyramid.bits2, Bits1(n).sum_weight2());
+/// assert_eq!(pyramid.bits8, Bits1(n).sum_weight2().sum_weight2().sum_weight2());
+/// assert_eq!(pyramid.weight(), n.count_ones());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WeightPyramid {
+    pub bits1: Bits1<u128>,
+    pub bits2: Bits2<u128>,
+    pub bits4: Bits4<u128>,
+    pub bits8: Bits8<u128>,
+    pub bits16: Bits16<u128>,
+    pub bits32: Bits32<u128>,
+    pub bits64: Bits64<u128>,
+    pub bits128: Bits128<u128>,
+}
+
+impl WeightPyramid {
+    /// Computes every level of hamming weights of `n`.
+    #[inline]
+    pub const fn new(n: u128) -> Self {
+        let bits1 = Bits1(n);
+        let bits2 = bits1.sum_weight2();
+        let bits4 = bits2.sum_weight2();
+        let bits8 = bits4.sum_weight2();
+        let bits16 = bits8.sum_weight2();
+        let bits32 = bits16.sum_weight2();
+        let bits64 = bits32.sum_weight2();
+        let bits128 = bits64.sum_weight2();
+        Self {
+            bits1,
+            bits2,
+            bits4,
+            bits8,
+            bits16,
+            bits32,
+            bits64,
+            bits128,
+        }
+    }
+
+    /// The hamming weight of the whole number.
+    #[inline]
+    pub const fn weight(&self) -> u32 {
+        self.bits128.0 as u32
+    }
+
+    /// Computes the minimum hamming weight distance of every element at every level.
+    ///
+    /// The sum of the elements at a level (see `sum_weights`) is a lower bound
+    /// on the hamming distance of the two original numbers. The bound gets
+    /// tighter at every level down to `bits1`, where it is exact.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// let a = WeightPyramid::new(0b1111_0000);
+    /// let b = WeightPyramid::new(0b0000_1111);
+    /// assert_eq!(a.minhwd(&b).sum_weights(), [8, 8, 8, 0, 0, 0, 0, 0]);
+    /// ```
+    #[inline]
+    pub fn minhwd(&self, other: &Self) -> Self {
+        Self {
+            bits1: self.bits1.minhwd(other.bits1),
+            bits2: self.bits2.minhwd(other.bits2),
+            bits4: self.bits4.minhwd(other.bits4),
+            bits8: self.bits8.minhwd(other.bits8),
+            bits16: self.bits16.minhwd(other.bits16),
+            bits32: self.bits32.minhwd(other.bits32),
+            bits64: self.bits64.minhwd(other.bits64),
+            bits128: self.bits128.minhwd(other.bits128),
+        }
+    }
+
+    /// Computes the maximum hamming weight distance of every element at every level.
+    ///
+    /// The sum of the elements at a level (see `sum_weights`) is an upper bound
+    /// on the hamming distance of the two original numbers. The bound gets
+    /// tighter at every level down to `bits1`, where it is exact.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// let a = WeightPyramid::new(0b1111_0000);
+    /// let b = WeightPyramid::new(0b0000_1111);
+    /// assert_eq!(a.maxhwd(&b).sum_weights(), [8, 8, 8, 8, 8, 8, 8, 8]);
+    /// ```
+    #[inline]
+    pub fn maxhwd(&self, other: &Self) -> Self {
+        Self {
+            bits1: self.bits1.maxhwd(other.bits1),
+            bits2: self.bits2.maxhwd(other.bits2),
+            bits4: self.bits4.maxhwd(other.bits4),
+            bits8: self.bits8.maxhwd(other.bits8),
+            bits16: self.bits16.maxhwd(other.bits16),
+            bits32: self.bits32.maxhwd(other.bits32),
+            bits64: self.bits64.maxhwd(other.bits64),
+            bits128: self.bits128.maxhwd(other.bits128),
+        }
+    }
+
+    /// Sums the elements at every level.
+    ///
+    /// Index `i` of the output is the sum of the level with `2^i` bits per element,
+    /// so index `0` is `bits1` and index `7` is `bits128`.
+    #[inline]
+    pub fn sum_weights(&self) -> [u32; 8] {
+        [
+            self.bits1.sum_weight() as u32,
+            self.bits2.sum_weight() as u32,
+            self.bits4.sum_weight() as u32,
+            self.bits8.sum_weight() as u32,
+            self.bits16.sum_weight() as u32,
+            self.bits32.sum_weight() as u32,
+            self.bits64.sum_weight() as u32,
+            self.bits128.sum_weight() as u32,
+        ]
+    }
+}
+
+impl From<u128> for WeightPyramid {
+    #[inline]
+    fn from(n: u128) -> Self {
+        Self::new(n)
+    }
+}
+
+/// Computes the hamming distance between two multi-word descriptors if it is at most `k`.
+///
+/// Both descriptors must be made of the same number of words. The lower bound on the
+/// distance from every level of the pyramids is checked from the coarsest to the finest
+/// level before the exact distance is computed word by word. As soon as a bound shows that
+/// the distance exceeds `k`, this returns `None` without looking at the remaining words.
+///
+/// ```
+/// use swar::*;
+///
+/// let a = [WeightPyramid::new(0b1111), WeightPyramid::new(0)];
+/// let b = [WeightPyramid::new(0b0111), WeightPyramid::new(0b1100)];
+/// assert_eq!(within_distance(&a, &b, 3), Some(3));
+/// assert_eq!(within_distance(&a, &b, 2), None);
+/// ```
+pub fn within_distance(a: &[WeightPyramid], b: &[WeightPyramid], k: u32) -> Option<u32> {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "descriptors must have the same number of words"
+    );
+    let coarse = level_bound(a, b, k, |a, b| a.bits128.minhwd(b.bits128).sum_weight())?;
+    level_bound(a, b, k, |a, b| a.bits64.minhwd(b.bits64).sum_weight())?;
+    level_bound(a, b, k, |a, b| a.bits32.minhwd(b.bits32).sum_weight())?;
+    level_bound(a, b, k, |a, b| a.bits16.minhwd(b.bits16).sum_weight())?;
+    level_bound(a, b, k, |a, b| a.bits8.minhwd(b.bits8).sum_weight())?;
+    level_bound(a, b, k, |a, b| a.bits4.minhwd(b.bits4).sum_weight())?;
+    level_bound(a, b, k, |a, b| a.bits2.minhwd(b.bits2).sum_weight())?;
+    // Replace the coarse bound of each word with its exact distance one at a time so that
+    // the partial sum remains a lower bound on the whole distance.
+    let mut bound = coarse;
+    for (a, b) in a.iter().zip(b) {
+        let exact = a.bits1.minhwd(b.bits1).count_ones();
+        bound = bound + exact - a.bits128.minhwd(b.bits128).0 as u32;
+        if bound > k {
+            return None;
+        }
+    }
+    Some(bound)
+}
+
+/// Sums the lower bound from one level of every word, stopping once it exceeds `k`.
+#[inline]
+fn level_bound(
+    a: &[WeightPyramid],
+    b: &[WeightPyramid],
+    k: u32,
+    level: impl Fn(&WeightPyramid, &WeightPyramid) -> u128,
+) -> Option<u32> {
+    let mut sum = 0;
+    for (a, b) in a.iter().zip(b) {
+        sum += level(a, b) as u32;
+        if sum > k {
+            return None;
+        }
+    }
+    Some(sum)
+}
@@ -0,0 +1,49 @@
+use crate::bytes::words;
+use crate::*;
+
+/// Adds up the absolute differences of the bytes of `a` and `b`, 16 at a time.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` aren't the same length.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(sad(&[0, 10, 255, 7], &[255, 12, 0, 7]), 255 + 2 + 255);
+/// ```
+pub fn sad(a: &[u8], b: &[u8]) -> u64 {
+    assert_eq!(a.len(), b.len(), "a and b must be the same length");
+    words(a)
+        .zip(words(b))
+        .map(|((_, a, _), (_, b, _))| a.sad(b) as u64)
+        .sum()
+}
+
+/// Adds up the absolute differences of the elements of `a` and `b`, 8 at a time.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` aren't the same length.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(sad_u16(&[0, 1000, 65535], &[65535, 999, 0]), 65535 + 1 + 65535);
+/// ```
+pub fn sad_u16(a: &[u16], b: &[u16]) -> u64 {
+    assert_eq!(a.len(), b.len(), "a and b must be the same length");
+    // Missing elements are zero in both, so they add nothing.
+    let load = |chunk: &[u16]| {
+        Bits16(
+            chunk
+                .iter()
+                .rev()
+                .fold(0, |word, &element| word << 16 | element as u128),
+        )
+    };
+    a.chunks(8)
+        .zip(b.chunks(8))
+        .map(|(a, b)| load(a).sad(load(b)) as u64)
+        .sum()
+}
@@ -1,1587 +1,1161 @@
 use crate::*;
-use core::ops::{Add, BitAnd, Shr};
-use packed_simd::{u16x8, u32x4, u64x2, u8x16};
+use core::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl,
+    ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
+#[cfg(feature = "simd")]
+use core::simd::{num::SimdUint, u16x8, u32x4, u64x2, u8x16, ToBytes};
 
 pub const LEFT_MASKS: [u128; 7] = [
-    0xFFFF_FFFF_FFFF_FFFF_0000_0000_0000_0000,
-    0xFFFF_FFFF_0000_0000_FFFF_FFFF_0000_0000,
-    0xFFFF_0000_FFFF_0000_FFFF_0000_FFFF_0000,
-    0xFF00_FF00_FF00_FF00_FF00_FF00_FF00_FF00,
-    0xF0F0_F0F0_F0F0_F0F0_F0F0_F0F0_F0F0_F0F0,
-    0xCCCC_CCCC_CCCC_CCCC_CCCC_CCCC_CCCC_CCCC,
-    0xAAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA,
+    left_mask(128),
+    left_mask(64),
+    left_mask(32),
+    left_mask(16),
+    left_mask(8),
+    left_mask(4),
+    left_mask(2),
 ];
 
 pub const RIGHT_MASKS: [u128; 7] = [
-    0x0000_0000_0000_0000_FFFF_FFFF_FFFF_FFFF,
-    0x0000_0000_FFFF_FFFF_0000_0000_FFFF_FFFF,
-    0x0000_FFFF_0000_FFFF_0000_FFFF_0000_FFFF,
-    0x00FF_00FF_00FF_00FF_00FF_00FF_00FF_00FF,
-    0x0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F,
-    0x3333_3333_3333_3333_3333_3333_3333_3333,
-    0x5555_5555_5555_5555_5555_5555_5555_5555,
+    right_mask(128),
+    right_mask(64),
+    right_mask(32),
+    right_mask(16),
+    right_mask(8),
+    right_mask(4),
+    right_mask(2),
 ];
 
-pub const ONES2: u128 = 0x5555_5555_5555_5555_5555_5555_5555_5555;
-pub const ONES4: u128 = 0x1111_1111_1111_1111_1111_1111_1111_1111;
-pub const ONES8: u128 = 0x0101_0101_0101_0101_0101_0101_0101_0101;
-pub const ONES16: u128 = 0x0001_0001_0001_0001_0001_0001_0001_0001;
-pub const ONES32: u128 = 0x0000_0001_0000_0001_0000_0001_0000_0001;
-pub const ONES64: u128 = 0x0000_0000_0000_0001_0000_0000_0000_0001;
-
-pub const SIGNS2: u128 = ONES2 << 1;
-pub const SIGNS4: u128 = ONES4 << 3;
-pub const SIGNS8: u128 = ONES8 << 7;
-pub const SIGNS16: u128 = ONES16 << 15;
-pub const SIGNS32: u128 = ONES32 << 31;
-pub const SIGNS64: u128 = ONES64 << 63;
-
-pub const WEIGHT_MASK2: u128 = 0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF;
-pub const WEIGHT_MASK4: u128 = (ONES4 << 3) - ONES4;
-pub const WEIGHT_MASK8: u128 = (ONES8 << 4) - ONES8;
-pub const WEIGHT_MASK16: u128 = (ONES16 << 5) - ONES16;
-pub const WEIGHT_MASK32: u128 = (ONES32 << 6) - ONES32;
-pub const WEIGHT_MASK64: u128 = (ONES64 << 7) - ONES64;
-
-pub const WEIGHT_MSB2: u128 = ONES2 << 1;
-pub const WEIGHT_MSB4: u128 = ONES4 << 2;
-pub const WEIGHT_MSB8: u128 = ONES8 << 3;
-pub const WEIGHT_MSB16: u128 = ONES16 << 4;
-pub const WEIGHT_MSB32: u128 = ONES32 << 5;
-pub const WEIGHT_MSB64: u128 = ONES64 << 6;
-
-impl Bits1<u128> {
-    /// Spread a single value out to each element. Must be able to fit.
-    ///
-    /// ```
-    /// use swar::*;
-    ///
-    /// assert_eq!(Bits1::from_element(1), Bits1(0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF));
-    /// assert_eq!(Bits1::from_element(0), Bits1(0x0000_0000_0000_0000_0000_0000_0000_0000));
-    /// ```
-    #[inline]
-    pub fn from_element(e: u128) -> Self {
-        // We can do this in log2(bits) time by doubling the sequence.
-        let n1 = e | e << 1;
-        let n2 = n1 | n1 << 2;
-        let n3 = n2 | n2 << 4;
-        let n4 = n3 | n3 << 8;
-        let n5 = n4 | n4 << 16;
-        let n6 = n5 | n5 << 32;
-        let n7 = n6 | n6 << 64;
-        Self(n7)
-    }
-
-    /// If any bit is set in each element, sets the element to 1, else 0.
-    ///
-    /// ```
-    /// use swar::*;
-    ///
-    /// let input = Bits1(0b1010u128);
-    /// let output = Bits1(0b1010u128);
-    /// assert_eq!(input.any(), output);
-    /// ```
-    #[inline]
-    pub fn any(self) -> Self {
-        self
-    }
-
-    /// Takes two inputs that have half-sized elements and compresses them
-    /// into half the space and puts them in the left and right sides of this.
-    ///
-    /// ```
-    /// use swar::*;
-    ///
-    /// let input = Bits1(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
-    /// let (left, right) = input.halve();
-    /// let output = Bits1::union(left, right);
-    /// assert_eq!(input, output);
-    /// ```
-    #[inline]
-    pub fn union(left: Bits2<u128>, right: Bits2<u128>) -> Self {
-        let Bits2(left) = left;
-        let Bits2(right) = right;
-        let left = (left & LEFT_MASKS[5]) >> 1 | left & RIGHT_MASKS[5];
-        let left = (left & LEFT_MASKS[4]) >> 2 | left & RIGHT_MASKS[4];
-        let left = (left & LEFT_MASKS[3]) >> 4 | left & RIGHT_MASKS[3];
-        let left = (left & LEFT_MASKS[2]) >> 8 | left & RIGHT_MASKS[2];
-        let left = (left & LEFT_MASKS[1]) >> 16 | left & RIGHT_MASKS[1];
-        let left = (left & LEFT_MASKS[0]) >> 32 | left & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[5]) >> 1 | right & RIGHT_MASKS[5];
-        let right = (right & LEFT_MASKS[4]) >> 2 | right & RIGHT_MASKS[4];
-        let right = (right & LEFT_MASKS[3]) >> 4 | right & RIGHT_MASKS[3];
-        let right = (right & LEFT_MASKS[2]) >> 8 | right & RIGHT_MASKS[2];
-        let right = (right & LEFT_MASKS[1]) >> 16 | right & RIGHT_MASKS[1];
-        let right = (right & LEFT_MASKS[0]) >> 32 | right & RIGHT_MASKS[0];
-        Self(left << 64 | right)
-    }
+pub const ONES2: u128 = ones(2);
+pub const ONES4: u128 = ones(4);
+pub const ONES8: u128 = ones(8);
+pub const ONES16: u128 = ones(16);
+pub const ONES32: u128 = ones(32);
+pub const ONES64: u128 = ones(64);
+
+pub const SIGNS2: u128 = signs(2);
+pub const SIGNS4: u128 = signs(4);
+pub const SIGNS8: u128 = signs(8);
+pub const SIGNS16: u128 = signs(16);
+pub const SIGNS32: u128 = signs(32);
+pub const SIGNS64: u128 = signs(64);
+
+pub const WEIGHT_MASK2: u128 = weight_mask(2);
+pub const WEIGHT_MASK4: u128 = weight_mask(4);
+pub const WEIGHT_MASK8: u128 = weight_mask(8);
+pub const WEIGHT_MASK16: u128 = weight_mask(16);
+pub const WEIGHT_MASK32: u128 = weight_mask(32);
+pub const WEIGHT_MASK64: u128 = weight_mask(64);
+
+pub const WEIGHT_MSB2: u128 = weight_msb(2);
+pub const WEIGHT_MSB4: u128 = weight_msb(4);
+pub const WEIGHT_MSB8: u128 = weight_msb(8);
+pub const WEIGHT_MSB16: u128 = weight_msb(16);
+pub const WEIGHT_MSB32: u128 = weight_msb(32);
+pub const WEIGHT_MSB64: u128 = weight_msb(64);
+
+/// Sets the lowest bit of each `width`-bit element.
+///
+/// Only whole elements are counted, so when `width` doesn't divide 128 the
+/// bits above the last element (see `used_mask`) are left clear.
+///
+/// ```
+/// use swar::u128::*;
+///
+/// assert_eq!(ones(8), 0x0101_0101_0101_0101_0101_0101_0101_0101);
+/// assert_eq!(ones(128), 1);
+/// assert_eq!(ones(3), 0x0924_9249_2492_4924_9249_2492_4924_9249);
+/// ```
+pub const fn ones(width: u32) -> u128 {
+    // We can do this in log2(bits) time by doubling the sequence.
+    let mut n = 1;
+    let mut filled = width;
+    while filled < 128 {
+        n |= n << filled;
+        filled *= 2;
+    }
+    n & used_mask(width)
+}
 
-    #[inline]
-    pub fn count_ones(self) -> u32 {
-        self.0.count_ones()
-    }
+/// Sets every bit that belongs to a whole `width`-bit element.
+///
+/// The `128 % width` bits above the last element are unused.
+///
+/// ```
+/// use swar::u128::*;
+///
+/// assert_eq!(used_mask(8), u128::MAX);
+/// assert_eq!(used_mask(10), u128::MAX >> 8);
+/// ```
+pub const fn used_mask(width: u32) -> u128 {
+    u128::MAX >> (128 % width)
+}
 
-    /// Sqishes all the bits to the right in each 2-bit segment.
-    ///
-    /// ```
-    /// use swar::*;
-    ///
-    /// let input = Bits1(0b00_01_10_11);
-    /// let out = Bits2(0b00_01_01_11);
-    /// assert_eq!(input.pack_ones(), out);
-    /// ```
-    #[inline]
-    pub fn pack_ones(self) -> Bits2<u128> {
-        let Self(x) = self;
-        let lower = (x & LEFT_MASKS[6]) >> 1 | x & RIGHT_MASKS[6];
-        let upper = x & (x & RIGHT_MASKS[6]) << 1;
-        Bits2(lower | upper)
-    }
+/// Sets the highest bit of each `width`-bit element.
+///
+/// ```
+/// use swar::u128::*;
+///
+/// assert_eq!(signs(4), 0x8888_8888_8888_8888_8888_8888_8888_8888);
+/// ```
+pub const fn signs(width: u32) -> u128 {
+    ones(width) << (width - 1)
+}
 
-    #[inline]
-    pub fn sum_weight(self) -> u128 {
-        self.0.count_ones().into()
-    }
+/// Sets the right half of each `width`-bit element.
+///
+/// ```
+/// use swar::u128::*;
+///
+/// assert_eq!(right_mask(4), 0x3333_3333_3333_3333_3333_3333_3333_3333);
+/// ```
+pub const fn right_mask(width: u32) -> u128 {
+    ones(width) * ((1 << (width / 2)) - 1)
+}
 
-    #[inline]
-    pub fn sum_weight2(self) -> Bits2<u128> {
-        let (left, right) = self.split();
-        left + right
-    }
+/// Sets the left half of each `width`-bit element.
+///
+/// ```
+/// use swar::u128::*;
+///
+/// assert_eq!(left_mask(4), 0xCCCC_CCCC_CCCC_CCCC_CCCC_CCCC_CCCC_CCCC);
+/// ```
+pub const fn left_mask(width: u32) -> u128 {
+    right_mask(width) << (width / 2)
+}
 
-    /// This computes the minimum hamming weight distance from hamming weights.
-    ///
-    /// For a Bits1, this is the same as computing the hamming weight from the
-    /// original number and is a simple XOR.
-    ///
-    /// The minimum and maximum weights are exactly the same for single bits.
-    #[inline]
-    pub fn minhwd(self, other: Self) -> Self {
-        Self(self.0 ^ other.0)
-    }
+/// Sets the bits of each `width`-bit element needed to hold a weight up to `width`.
+///
+/// ```
+/// use swar::u128::*;
+///
+/// assert_eq!(weight_mask(8), 0x0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F);
+/// ```
+pub const fn weight_mask(width: u32) -> u128 {
+    // When the weight needs every bit, the shift wraps around to give all ones.
+    ones(width)
+        .wrapping_shl(width.trailing_zeros() + 1)
+        .wrapping_sub(ones(width))
+}
 
-    /// This computes the maximum hamming weight distance from hamming weights.
-    ///
-    /// For a Bits1, this is the same as computing the hamming weight from the
-    /// original number and is a simple XOR.
-    ///
-    /// The minimum and maximum weights are exactly the same for single bits.
-    #[inline]
-    pub fn maxhwd(self, other: Self) -> Self {
-        Self(self.0 ^ other.0)
-    }
+/// Sets the highest bit of a weight up to `width` in each `width`-bit element.
+///
+/// ```
+/// use swar::u128::*;
+///
+/// assert_eq!(weight_msb(8), 0x0808_0808_0808_0808_0808_0808_0808_0808);
+/// ```
+pub const fn weight_msb(width: u32) -> u128 {
+    ones(width) << width.trailing_zeros()
+}
 
-    #[inline]
-    pub fn split(self) -> (Bits2<u128>, Bits2<u128>) {
-        let Self(n) = self;
-        (Bits2((n & LEFT_MASKS[6]) >> 1), Bits2(n & RIGHT_MASKS[6]))
-    }
+/// Builds the thermometer code `(1 << c) - 1` of the count `c` in each `width`-bit segment.
+///
+/// No count may exceed `width`.
+#[inline]
+const fn thermometer(counts: u128, width: u32) -> u128 {
+    let ones = ones(width);
+    let lane = u128::MAX >> (128 - width);
+    // Shift full segments left by the count one bit of the count at a time,
+    // dropping the bits that leave each segment, and then flip the result.
+    let mut t = u128::MAX;
+    let mut bit = 0;
+    while 1 << bit <= width {
+        let shift = 1 << bit;
+        // A 128-bit segment shifted by 128 has nothing left.
+        let shifted = if shift == 128 {
+            0
+        } else {
+            (t << shift) & !(ones * (lane >> (width - shift)))
+        };
+        let select = (counts >> bit & ones) * lane;
+        t ^= (t ^ shifted) & select;
+        bit += 1;
+    }
+    !t
+}
 
-    /// Takes the left and right sides and spreads them out
-    /// so that the bits in each element are spread out into twice
-    /// the amount of space.
-    ///
-    /// ```
-    /// use swar::*;
-    ///
-    /// let input = Bits1(0b1101 << 64 | 0b0101u128);
-    /// let (left, right) = input.halve();
-    /// assert_eq!(left, Bits2(0b0101_0001));
-    /// assert_eq!(right, Bits2(0b0001_0001));
-    /// ```
-    #[inline]
-    pub fn halve(self) -> (Bits2<u128>, Bits2<u128>) {
-        let Self(n) = self;
-        let left = (n & LEFT_MASKS[0]) >> 64;
-        let left = (left & LEFT_MASKS[1]) << 32 | left & RIGHT_MASKS[1];
-        let left = (left & LEFT_MASKS[2]) << 16 | left & RIGHT_MASKS[2];
-        let left = (left & LEFT_MASKS[3]) << 8 | left & RIGHT_MASKS[3];
-        let left = (left & LEFT_MASKS[4]) << 4 | left & RIGHT_MASKS[4];
-        let left = (left & LEFT_MASKS[5]) << 2 | left & RIGHT_MASKS[5];
-        let left = (left & LEFT_MASKS[6]) << 1 | left & RIGHT_MASKS[6];
-        let right = n & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[1]) << 32 | right & RIGHT_MASKS[1];
-        let right = (right & LEFT_MASKS[2]) << 16 | right & RIGHT_MASKS[2];
-        let right = (right & LEFT_MASKS[3]) << 8 | right & RIGHT_MASKS[3];
-        let right = (right & LEFT_MASKS[4]) << 4 | right & RIGHT_MASKS[4];
-        let right = (right & LEFT_MASKS[5]) << 2 | right & RIGHT_MASKS[5];
-        let right = (right & LEFT_MASKS[6]) << 1 | right & RIGHT_MASKS[6];
-        (Bits2(left), Bits2(right))
+/// Adds up the `from`-bit elements of `x` into `to`-bit elements.
+#[inline]
+const fn sum_lanes(mut x: u128, from: u32, to: u32) -> u128 {
+    let mut width = from;
+    while width < to {
+        x = ((x & left_mask(2 * width)) >> width) + (x & right_mask(2 * width));
+        width *= 2;
     }
+    x
 }
 
-/// Note that you are responsible for dealing with overflow.
-/// Try to avoid overflow or use the split() method to add in two halves.
-/// You can use split() on the result to get the carry bits.
-impl Add for Bits1<u128> {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self {
-        Self(self.0 + rhs.0)
+/// Moves the `width`-bit elements in the right half of `x` into the right half
+/// of `2 * width`-bit elements.
+#[inline]
+const fn spread(mut x: u128, width: u32) -> u128 {
+    let mut group = 64;
+    while group >= 2 * width {
+        x = (x & left_mask(group)) << (group / 2) | x & right_mask(group);
+        group /= 2;
     }
+    x
 }
 
-impl BitAnd<u128> for Bits1<u128> {
-    type Output = Self;
-
-    #[inline]
-    fn bitand(self, rhs: u128) -> Self {
-        Self(self.0 & rhs)
+/// Moves the right half of the `2 * width`-bit elements of `x` into `width`-bit
+/// elements in the right half of `x`, which is the opposite of `spread`.
+#[inline]
+const fn compress(mut x: u128, width: u32) -> u128 {
+    let mut group = 2 * width;
+    while group <= 64 {
+        x = (x & left_mask(2 * group)) >> (group / 2) | x & right_mask(2 * group);
+        group *= 2;
     }
+    x
 }
 
-impl Shr<u32> for Bits1<u128> {
-    type Output = Self;
+impl<const WIDTH: u32> Bits<WIDTH, u128> {
+    /// The number of whole elements.
+    ///
+    /// When `WIDTH` doesn't divide 128, the `128 % WIDTH` bits above the last element
+    /// are unused. The routines here never set them, and the lane-isolated routines
+    /// ignore them in their inputs.
+    pub const LANES: u32 = 128 / WIDTH;
 
-    #[inline]
-    fn shr(self, rhs: u32) -> Self {
-        Self(self.0 >> rhs)
-    }
-}
+    /// The largest value that fits in an element.
+    pub const MAX_ELEMENT: u128 = u128::MAX >> (128 - WIDTH);
+
+    /// Fails to compile any routine on hamming weights that is used with elements
+    /// which aren't a power of two wide.
+    const POWER_OF_TWO: () = assert!(
+        WIDTH.is_power_of_two(),
+        "this routine needs elements that are a power of two wide"
+    );
 
-impl Bits2<u128> {
     /// Spread a single value out to each element. Must be able to fit.
     ///
     /// ```
     /// use swar::*;
     ///
     /// assert_eq!(Bits2::from_element(0b10), Bits2(0xAAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA));
-    /// ```
-    #[inline]
-    pub fn from_element(e: u128) -> Self {
-        // We can do this in log2(bits) time by doubling the sequence.
-        let n2 = e | e << 2;
-        let n3 = n2 | n2 << 4;
-        let n4 = n3 | n3 << 8;
-        let n5 = n4 | n4 << 16;
-        let n6 = n5 | n5 << 32;
-        let n7 = n6 | n6 << 64;
-        Self(n7)
-    }
-
-    /// If any bit is set in each element, sets the element to 1, else 0.
+    /// assert_eq!(Bits8::from_element(0xFE), Bits8(0xFEFE_FEFE_FEFE_FEFE_FEFE_FEFE_FEFE_FEFE));
     ///
-    /// ```
-    /// use swar::*;
+    /// // This can also be used to make constants.
+    /// const SPACES: Bits8<u128> = Bits8::from_element(0x20);
+    /// assert_eq!(SPACES, Bits8(0x2020_2020_2020_2020_2020_2020_2020_2020));
     ///
-    /// let input = Bits2(0b01101100u128);
-    /// let output = Bits2(0b01010100u128);
-    /// assert_eq!(input.any(), output);
+    /// // The 8 bits above the last 10-bit element are left clear.
+    /// assert_eq!(Bits10::from_element(0x3FF), Bits10(u128::MAX >> 8));
     /// ```
     #[inline]
-    pub fn any(self) -> Self {
-        let Self(x) = self;
-        Self((x & LEFT_MASKS[6]) >> 1 | x & RIGHT_MASKS[6])
+    pub const fn from_element(e: u128) -> Self {
+        debug_assert!(e <= Self::MAX_ELEMENT, "element does not fit");
+        Self(ones(WIDTH) * e)
     }
 
-    /// Takes two inputs that have half-sized elements and compresses them
-    /// into half the space and puts them in the left and right sides of this.
+    /// Spread a single value out to each element, or fail if it doesn't fit.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits2(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
-    /// let (left, right) = input.halve();
-    /// let output = Bits2::union(left, right);
-    /// assert_eq!(input, output);
-    /// ```
-    #[inline]
-    pub fn union(left: Bits4<u128>, right: Bits4<u128>) -> Self {
-        let Bits4(left) = left;
-        let Bits4(right) = right;
-        let left = (left & LEFT_MASKS[4]) >> 2 | left & RIGHT_MASKS[4];
-        let left = (left & LEFT_MASKS[3]) >> 4 | left & RIGHT_MASKS[3];
-        let left = (left & LEFT_MASKS[2]) >> 8 | left & RIGHT_MASKS[2];
-        let left = (left & LEFT_MASKS[1]) >> 16 | left & RIGHT_MASKS[1];
-        let left = (left & LEFT_MASKS[0]) >> 32 | left & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[4]) >> 2 | right & RIGHT_MASKS[4];
-        let right = (right & LEFT_MASKS[3]) >> 4 | right & RIGHT_MASKS[3];
-        let right = (right & LEFT_MASKS[2]) >> 8 | right & RIGHT_MASKS[2];
-        let right = (right & LEFT_MASKS[1]) >> 16 | right & RIGHT_MASKS[1];
-        let right = (right & LEFT_MASKS[0]) >> 32 | right & RIGHT_MASKS[0];
-        Self(left << 64 | right)
-    }
-
-    /// Squashes half-sized elements into half-sized spaces to the right
-    /// and fills the left half of the number with zeros.
+    /// assert_eq!(Bits4::try_from_element(0xF), Ok(Bits4::from_element(0xF)));
     ///
-    /// This is the same as `union(0, n)`.
-    #[inline]
-    pub fn squash(self) -> Bits1<u128> {
-        Bits1::union(Self(0), self)
-    }
-
+    /// let error = Bits4::try_from_element(0x1F).unwrap_err();
+    /// assert_eq!((error.width, error.value), (4, 0x1F));
+    /// ```
     #[inline]
-    pub fn count_ones(self) -> u32 {
-        self.0.count_ones()
+    pub const fn try_from_element(e: u128) -> Result<Self, ElementOutOfRange> {
+        if e <= Self::MAX_ELEMENT {
+            Ok(Self(ones(WIDTH) * e))
+        } else {
+            Err(ElementOutOfRange {
+                width: WIDTH,
+                value: e,
+            })
+        }
     }
 
-    /// Sqishes all the bits to the right in each 4-bit segment.
+    /// If any bit is set in each element, sets the element to 1, else 0.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits2(0b11_01_01_00);
-    /// let out = Bits4(0b0111_0001);
-    /// assert_eq!(input.pack_ones(), out, "got {:08b} expected {:08b}", input.pack_ones().0, out.0);
-    ///
-    /// let input = Bits2(0b11_11_00_00);
-    /// let out = Bits4(0b1111_0000);
-    /// assert_eq!(input.pack_ones(), out, "got {:08b} expected {:08b}", input.pack_ones().0, out.0);
-    ///
-    /// let input = Bits2(0b01_01_00_01);
-    /// let out = Bits4(0b0011_0001);
-    /// assert_eq!(input.pack_ones(), out, "got {:08b} expected {:08b}", input.pack_ones().0, out.0);
+    /// let input = Bits8(0xF0_00u128);
+    /// let output = Bits8(0x01_00u128);
+    /// assert_eq!(input.any(), output);
     ///
-    /// let input = Bits2(0b11_00_00_11);
-    /// let out = Bits4(0b0011_0011);
-    /// assert_eq!(input.pack_ones(), out, "got {:08b} expected {:08b}", input.pack_ones().0, out.0);
+    /// let input = Bits3(0b100_000_011_001);
+    /// let output = Bits3(0b001_000_001_001);
+    /// assert_eq!(input.any(), output);
     /// ```
     #[inline]
-    pub fn pack_ones(self) -> Bits4<u128> {
+    pub const fn any(self) -> Self {
+        // Adding all ones to the bits below the sign of each element carries into
+        // the sign unless they are all zero, and it can't carry any further.
         let Self(x) = self;
-        let x: u8x16 = unsafe { core::mem::transmute(x) };
-        let left = x & 0xF0;
-        let right = x & 0x0F;
-        let left_count = left.count_ones();
-        let right_count = right.count_ones();
-        let one = u8x16::from([1; 16]);
-        let left_out = (one << left_count) - 1;
-        let right_out = (one << right_count) - 1;
-        let x = left_out << 4 | right_out;
-        Bits4(unsafe { core::mem::transmute(x) })
+        let signs = signs(WIDTH);
+        let low = used_mask(WIDTH) & !signs;
+        Self(((((x & low) + low) | x) & signs) >> (WIDTH - 1))
     }
 
-    #[inline]
-    pub fn sum_weight(self) -> u128 {
-        self.sum_weight2()
-            .sum_weight2()
-            .sum_weight2()
-            .sum_weight2()
-            .sum_weight2()
-            .sum_weight2()
-            .0
-    }
-
-    #[inline]
-    pub fn sum_weight2(self) -> Bits4<u128> {
-        let (left, right) = self.split();
-        left + right
-    }
-
-    /// This computes the minimum hamming weight distance from hamming weights.
+    /// Adds each element, wrapping around on overflow without touching the
+    /// neighboring elements.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// // All combinations of inputs 0-2 (hamming weights)
-    /// let a = Bits2(0b00_01_10_00_01_10_00_01_10u128);
-    /// let b = Bits2(0b00_00_00_01_01_01_10_10_10u128);
-    /// // Expected output weights
-    /// let e = Bits2(0b00_01_10_01_00_01_10_01_00u128);
-    ///
-    /// assert_eq!(a.minhwd(b), e, "got hamming distances {:b} expected {:b}", a.minhwd(b).0, e.0);
+    /// let a = Bits10(0x3FF << 20 | 500 << 10 | 7);
+    /// let b = Bits10(2 << 20 | 600 << 10 | 1);
+    /// assert_eq!(a.wrapping_add(b), Bits10(1 << 20 | 76 << 10 | 8));
     /// ```
     #[inline]
-    pub fn minhwd(self, other: Self) -> Self {
-        // I worked out the Karnaugh map and got the following:
-        // High:
-        // |0|0|x|1|
-        // |0|0|x|0|
-        // |x|x|x|x|
-        // |1|0|x|0|
-        // Low:
-        // |0|1|x|0|
-        // |1|0|x|1|
-        // |x|x|x|x|
-        // |0|1|x|0|
-        // I reduced these maps to the following computation.
-        // high = B1 & !A1 & !A0 | A1 & !B1 & !B0
-        // low = !A0 & B0 | A0 & !B0
-        // Please send PRs if you can improve this.
+    pub const fn wrapping_add(self, other: Self) -> Self {
+        // Add everything except the sign bits so that no carry leaves an element,
+        // then add the sign bits without their carry.
         let Self(a) = self;
         let Self(b) = other;
-        let low = RIGHT_MASKS[6] & (a ^ b);
-        let high = LEFT_MASKS[6] & (b & !a & !a << 1 | a & !b & !b << 1);
-        Self(low | high)
+        let signs = signs(WIDTH);
+        let low = used_mask(WIDTH) & !signs;
+        Self(((a & low) + (b & low)) ^ (a ^ b) & signs)
     }
 
-    /// This computes the maximum hamming weight distance from hamming weights.
+    /// Adds each element like `wrapping_add` and also returns which elements overflowed.
+    ///
+    /// Each element of the second output is 1 if that element overflowed, else 0.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// // All combinations of inputs 0-2 (hamming weights)
-    /// let a = Bits2(0b00_01_10_00_01_10_00_01_10u128);
-    /// let b = Bits2(0b00_00_00_01_01_01_10_10_10u128);
-    /// // Expected output weights
-    /// let e = Bits2(0b00_01_10_01_10_01_10_01_00u128);
-    ///
-    /// assert_eq!(a.maxhwd(b), e, "got hamming distances {:b} expected {:b}", a.maxhwd(b).0, e.0);
+    /// let a = Bits8(0xFF_80_7F_01);
+    /// let b = Bits8(0x01_80_01_01);
+    /// assert_eq!(a.overflowing_add(b), (Bits8(0x00_00_80_02), Bits8(0x01_01_00_00)));
     /// ```
     #[inline]
-    pub fn maxhwd(self, other: Self) -> Self {
-        // This is the same as `minhwd` except that if the input is
-        // `1` and `1` the bits could be in different spots so the max
-        // is `2`.
+    pub const fn overflowing_add(self, other: Self) -> (Self, Self) {
+        // An element carries out of its sign bit if both sign bits are set, or if
+        // either is set and the carry into the sign bit cleared it in the sum.
         let Self(a) = self;
         let Self(b) = other;
-        let low = RIGHT_MASKS[6] & (a ^ b);
-        let high = LEFT_MASKS[6] & (b & !a & !a << 1 | a & !b & !b << 1 | a << 1 & b << 1);
-        Self(low | high)
-    }
-
-    #[inline]
-    pub fn split(self) -> (Bits4<u128>, Bits4<u128>) {
-        let Self(n) = self;
-        (Bits4((n & LEFT_MASKS[5]) >> 2), Bits4(n & RIGHT_MASKS[5]))
-    }
-
-    /// Takes the left and right sides and spreads them out
-    /// so that the bits in each element are spread out into twice
-    /// the amount of space.
-    ///
-    /// ```
-    /// use swar::*;
-    ///
-    /// let input = Bits2(0b1101 << 64 | 0b0101u128);
-    /// let (left, right) = input.halve();
-    /// assert_eq!(left, Bits4(0b0011_0001));
-    /// assert_eq!(right, Bits4(0b0001_0001));
-    /// ```
-    #[inline]
-    pub fn halve(self) -> (Bits4<u128>, Bits4<u128>) {
-        let Self(n) = self;
-        let left = (n & LEFT_MASKS[0]) >> 64;
-        let left = (left & LEFT_MASKS[1]) << 32 | left & RIGHT_MASKS[1];
-        let left = (left & LEFT_MASKS[2]) << 16 | left & RIGHT_MASKS[2];
-        let left = (left & LEFT_MASKS[3]) << 8 | left & RIGHT_MASKS[3];
-        let left = (left & LEFT_MASKS[4]) << 4 | left & RIGHT_MASKS[4];
-        let left = (left & LEFT_MASKS[5]) << 2 | left & RIGHT_MASKS[5];
-        let right = n & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[1]) << 32 | right & RIGHT_MASKS[1];
-        let right = (right & LEFT_MASKS[2]) << 16 | right & RIGHT_MASKS[2];
-        let right = (right & LEFT_MASKS[3]) << 8 | right & RIGHT_MASKS[3];
-        let right = (right & LEFT_MASKS[4]) << 4 | right & RIGHT_MASKS[4];
-        let right = (right & LEFT_MASKS[5]) << 2 | right & RIGHT_MASKS[5];
-        (Bits4(left), Bits4(right))
-    }
-}
-
-/// Note that you are responsible for dealing with overflow.
-/// Try to avoid overflow or use the split() method to add in two halves.
-/// You can use split() on the result to get the carry bits.
-impl Add for Bits2<u128> {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self {
-        Self(self.0 + rhs.0)
-    }
-}
-
-impl BitAnd<u128> for Bits2<u128> {
-    type Output = Self;
-
-    #[inline]
-    fn bitand(self, rhs: u128) -> Self {
-        Self(self.0 & rhs)
-    }
-}
-
-impl Shr<u32> for Bits2<u128> {
-    type Output = Self;
-
-    #[inline]
-    fn shr(self, rhs: u32) -> Self {
-        Self(self.0 >> rhs)
-    }
-}
-
-impl Bits4<u128> {
-    /// Spread a single value out to each element. Must be able to fit.
-    ///
-    /// ```
-    /// use swar::*;
-    ///
-    /// assert_eq!(Bits4::from_element(0b0110), Bits4(0x6666_6666_6666_6666_6666_6666_6666_6666));
-    /// ```
-    #[inline]
-    pub fn from_element(e: u128) -> Self {
-        // We can do this in log2(bits) time by doubling the sequence.
-        let n3 = e | e << 4;
-        let n4 = n3 | n3 << 8;
-        let n5 = n4 | n4 << 16;
-        let n6 = n5 | n5 << 32;
-        let n7 = n6 | n6 << 64;
-        Self(n7)
+        let sum = self.wrapping_add(other);
+        let carries = (a & b | (a | b) & !sum.0) & signs(WIDTH);
+        (sum, Self(carries >> (WIDTH - 1)))
     }
 
-    /// If any bit is set in each element, sets the element to 1, else 0.
+    /// Adds each element, returning `None` if any element overflowed.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits4(0xE_0u128);
-    /// let output = Bits4(0x1_0u128);
-    /// assert_eq!(input.any(), output);
+    /// assert_eq!(Bits4(0x71).checked_add(Bits4(0x81)), Some(Bits4(0xF2)));
+    /// assert_eq!(Bits4(0x7F).checked_add(Bits4(0x11)), None);
     /// ```
     #[inline]
-    pub fn any(self) -> Self {
-        let Bits2(x) = Bits2(self.0).any();
-        Self((x & LEFT_MASKS[5]) >> 2 | x & RIGHT_MASKS[5])
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        match self.overflowing_add(other) {
+            (sum, Self(0)) => Some(sum),
+            _ => None,
+        }
     }
 
-    /// Takes two inputs that have half-sized elements and compresses them
-    /// into half the space and puts them in the left and right sides of this.
+    /// Subtracts each element, wrapping around on underflow without touching the
+    /// neighboring elements.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits4(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
-    /// let (left, right) = input.halve();
-    /// let output = Bits4::union(left, right);
-    /// assert_eq!(input, output);
+    /// let a = Bits7(1 << 14 | 100 << 7 | 7);
+    /// let b = Bits7(2 << 14 | 1 << 7 | 7);
+    /// assert_eq!(a.wrapping_sub(b), Bits7(0x7F << 14 | 99 << 7));
     /// ```
     #[inline]
-    pub fn union(left: Bits8<u128>, right: Bits8<u128>) -> Self {
-        let Bits8(left) = left;
-        let Bits8(right) = right;
-        let left = (left & LEFT_MASKS[3]) >> 4 | left & RIGHT_MASKS[3];
-        let left = (left & LEFT_MASKS[2]) >> 8 | left & RIGHT_MASKS[2];
-        let left = (left & LEFT_MASKS[1]) >> 16 | left & RIGHT_MASKS[1];
-        let left = (left & LEFT_MASKS[0]) >> 32 | left & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[3]) >> 4 | right & RIGHT_MASKS[3];
-        let right = (right & LEFT_MASKS[2]) >> 8 | right & RIGHT_MASKS[2];
-        let right = (right & LEFT_MASKS[1]) >> 16 | right & RIGHT_MASKS[1];
-        let right = (right & LEFT_MASKS[0]) >> 32 | right & RIGHT_MASKS[0];
-        Self(left << 64 | right)
-    }
-
-    /// Squashes half-sized elements into half-sized spaces to the right
-    /// and fills the left half of the number with zeros.
-    ///
-    /// This is the same as `union(0, n)`.
-    #[inline]
-    pub fn squash(self) -> Bits2<u128> {
-        Bits2::union(Self(0), self)
-    }
-
-    #[inline]
-    pub fn count_ones(self) -> u32 {
-        self.0.count_ones()
+    pub const fn wrapping_sub(self, other: Self) -> Self {
+        // Set the sign bits before subtracting everything below them so that no
+        // borrow leaves an element, then subtract the sign bits without their borrow.
+        let Self(a) = self;
+        let Self(b) = other;
+        let signs = signs(WIDTH);
+        let low = used_mask(WIDTH) & !signs;
+        Self(((a & low | signs) - (b & low)) ^ (a ^ !b) & signs)
     }
 
-    /// Sqishes all the bits to the right in each 8-bit segment.
+    /// Sets each element to 1 if the elements are equal, else 0.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits4(0b1111_1111);
-    /// let out = Bits8(0b11111111);
-    /// assert_eq!(input.pack_ones(), out, "got {:08b} expected {:08b}", input.pack_ones().0, out.0);
-    ///
-    /// let input = Bits4(0b0111_0001);
-    /// let out = Bits8(0b00001111);
-    /// assert_eq!(input.pack_ones(), out, "got {:08b} expected {:08b}", input.pack_ones().0, out.0);
-    ///
-    /// let input = Bits4(0b1111_0000);
-    /// let out = Bits8(0b0000_1111);
-    /// assert_eq!(input.pack_ones(), out, "got {:08b} expected {:08b}", input.pack_ones().0, out.0);
-    ///
-    /// let input = Bits4(0b0011_0001);
-    /// let out = Bits8(0b0000_0111);
-    /// assert_eq!(input.pack_ones(), out, "got {:08b} expected {:08b}", input.pack_ones().0, out.0);
-    ///
-    /// let input = Bits4(0b0011_0011);
-    /// let out = Bits8(0b00001111);
-    /// assert_eq!(input.pack_ones(), out, "got {:08b} expected {:08b}", input.pack_ones().0, out.0);
+    /// let a = Bits12::from_element(0xABC).insert(1, 0x123);
+    /// let b = Bits12::from_element(0xABC).insert(1, 0x124);
+    /// assert_eq!(a.lanes_eq(b), Bits12::from_element(1).insert(1, 0));
     /// ```
     #[inline]
-    pub fn pack_ones(self) -> Bits8<u128> {
-        let Self(x) = self;
-        let x: u8x16 = unsafe { core::mem::transmute(x) };
-        let counted = x.count_ones();
-        // The XOR must be done because there is an issue where shifting in
-        // SIMD shifts by n % LANE_WIDTH, so when we shift by LANE_WIDTH it
-        // actually shifts by 0, so to counteract that we have to take that
-        // 1 bit that is set when LANE_WIDTH is the count and XOR it with
-        // the 1 that is in the 0th bit index to turn the register to 0
-        // so that when we subtract from it we get all 1s.
-        let x = (u8x16::from([1; 16]) << (counted % 8) ^ counted >> 3) - 1;
-        Bits8(unsafe { core::mem::transmute(x) })
-    }
-
-    #[inline]
-    pub fn sum_weight(self) -> u128 {
-        self.sum_weight2()
-            .sum_weight2()
-            .sum_weight2()
-            .sum_weight2()
-            .sum_weight2()
-            .0
-    }
-
-    #[inline]
-    pub fn sum_weight2(self) -> Bits8<u128> {
-        let (left, right) = self.split();
-        left + right
+    pub const fn lanes_eq(self, other: Self) -> Self {
+        Self(Self(self.0 ^ other.0).any().0 ^ ones(WIDTH))
     }
 
-    /// This computes the minimum hamming weight distance from hamming weights.
+    /// Sets each element to 1 if it is less than the element of `other`, else 0.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// for a in 0u128..=4 {
-    ///     for b in 0u128..=4 {
-    ///         let aa = Bits4(a | a << 4);
-    ///         let bb = Bits4(b | b << 4);
-    ///         let out = aa.minhwd(bb);
-    ///         let diff = (a as i128 - b as i128).abs() as u128;
-    ///         let expected = Bits4(diff | diff << 4);
-    ///         assert_eq!(out, expected, "got hamming distances {:08b} expected {:08b} ({:04b}, {:04b})", out.0, expected.0, a, b);
-    ///     }
-    /// }
+    /// let a = Bits21(0x10FFFF << 42 | 0x41 << 21 | 5);
+    /// let b = Bits21(0x10FFFE << 42 | 0x42 << 21 | 5);
+    /// assert_eq!(a.lanes_lt(b), Bits21(1 << 21));
     /// ```
     #[inline]
-    pub fn minhwd(self, other: Self) -> Self {
+    pub const fn lanes_lt(self, other: Self) -> Self {
+        // An element is less than the other if subtracting them borrows out of the
+        // sign bit, which depends on the sign bits and the borrow into them.
         let Self(a) = self;
         let Self(b) = other;
-        // Compute ABC + !DEF.
-        let m = a + (b ^ WEIGHT_MASK4);
-        // Get the MSB of the weight.
-        let high = m & WEIGHT_MSB4;
-        // If the MSB is not set, we need to add 1 (because -n = ~n + 1).
-        let offset = (high ^ WEIGHT_MSB4) >> 2;
-        // If the MSB is set, we need to flip all the bits.
-        let flips = high | high >> 1 | high >> 2;
-        // The order we apply the offset and flips in is irrelevant because
-        // only one of the operations will have an effect anyways. We need
-        // to mask out the higher bit at the end because it shouldnt be set.
-        Self(((m ^ flips) + offset) & WEIGHT_MASK4)
-    }
-
-    #[inline]
-    pub fn split(self) -> (Bits8<u128>, Bits8<u128>) {
-        let Self(n) = self;
-        (Bits8((n & LEFT_MASKS[4]) >> 4), Bits8(n & RIGHT_MASKS[4]))
+        let Self(d) = self.wrapping_sub(other);
+        Self(((!a & b | !(a ^ b) & d) & signs(WIDTH)) >> (WIDTH - 1))
     }
 
-    /// Takes the left and right sides and spreads them out
-    /// so that the bits in each element are spread out into twice
-    /// the amount of space.
+    /// Sets each element to 1 if it is greater than the element of `other`, else 0.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits4(0xABCD << 64 | 0xDEAD);
-    /// let (left, right) = input.halve();
-    /// assert_eq!(left, Bits8(0x0A0B_0C0D));
-    /// assert_eq!(right, Bits8(0x0D0E_0A0D));
+    /// let a = Bits5(31 << 10 | 3 << 5 | 4);
+    /// let b = Bits5(30 << 10 | 3 << 5 | 5);
+    /// assert_eq!(a.lanes_gt(b), Bits5(1 << 10));
     /// ```
     #[inline]
-    pub fn halve(self) -> (Bits8<u128>, Bits8<u128>) {
-        let Self(n) = self;
-        let left = (n & LEFT_MASKS[0]) >> 64;
-        let left = (left & LEFT_MASKS[1]) << 32 | left & RIGHT_MASKS[1];
-        let left = (left & LEFT_MASKS[2]) << 16 | left & RIGHT_MASKS[2];
-        let left = (left & LEFT_MASKS[3]) << 8 | left & RIGHT_MASKS[3];
-        let left = (left & LEFT_MASKS[4]) << 4 | left & RIGHT_MASKS[4];
-        let right = n & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[1]) << 32 | right & RIGHT_MASKS[1];
-        let right = (right & LEFT_MASKS[2]) << 16 | right & RIGHT_MASKS[2];
-        let right = (right & LEFT_MASKS[3]) << 8 | right & RIGHT_MASKS[3];
-        let right = (right & LEFT_MASKS[4]) << 4 | right & RIGHT_MASKS[4];
-        (Bits8(left), Bits8(right))
+    pub const fn lanes_gt(self, other: Self) -> Self {
+        other.lanes_lt(self)
     }
-}
-
-/// Note that you are responsible for dealing with overflow.
-/// Try to avoid overflow or use the split() method to add in two halves.
-/// You can use split() on the result to get the carry bits.
-impl Add for Bits4<u128> {
-    type Output = Self;
 
-    fn add(self, rhs: Self) -> Self {
-        Self(self.0 + rhs.0)
-    }
-}
-
-impl BitAnd<u128> for Bits4<u128> {
-    type Output = Self;
-
-    #[inline]
-    fn bitand(self, rhs: u128) -> Self {
-        Self(self.0 & rhs)
-    }
-}
-
-impl Shr<u32> for Bits4<u128> {
-    type Output = Self;
-
-    #[inline]
-    fn shr(self, rhs: u32) -> Self {
-        Self(self.0 >> rhs)
-    }
-}
-
-impl Bits8<u128> {
-    /// Spread a single value out to each element. Must be able to fit.
+    /// Takes the absolute difference of each pair of elements.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// assert_eq!(Bits8::from_element(0xFE), Bits8(0xFEFE_FEFE_FEFE_FEFE_FEFE_FEFE_FEFE_FEFE));
+    /// let a = Bits7(100 << 14 | 3 << 7 | 127);
+    /// let b = Bits7(1 << 14 | 5 << 7);
+    /// assert_eq!(a.abs_diff(b), Bits7(99 << 14 | 2 << 7 | 127));
     /// ```
     #[inline]
-    pub fn from_element(e: u128) -> Self {
-        // We can do this in log2(bits) time by doubling the sequence.
-        let n4 = e | e << 8;
-        let n5 = n4 | n4 << 16;
-        let n6 = n5 | n5 << 32;
-        let n7 = n6 | n6 << 64;
-        Self(n7)
+    pub const fn abs_diff(self, other: Self) -> Self {
+        // Pick the difference that didn't wrap around in each element.
+        let below = self.lanes_lt(other).0 * Self::MAX_ELEMENT;
+        let Self(down) = self.wrapping_sub(other);
+        let Self(up) = other.wrapping_sub(self);
+        Self(down & !below | up & below)
     }
 
-    /// If any bit is set in each element, sets the element to 1, else 0.
-    ///
-    /// ```
-    /// use swar::*;
+    /// Gets the element at `index`, where element `0` is in the lowest bits.
     ///
-    /// let input = Bits8(0xF0_00u128);
-    /// let output = Bits8(0x01_00u128);
-    /// assert_eq!(input.any(), output);
-    /// ```
-    #[inline]
-    pub fn any(self) -> Self {
-        let Bits4(x) = Bits4(self.0).any();
-        Self((x & LEFT_MASKS[4]) >> 4 | x & RIGHT_MASKS[4])
-    }
-
-    /// Takes two inputs that have half-sized elements and compresses them
-    /// into half the space and puts them in the left and right sides of this.
+    /// Panics if `index` is not less than `LANES`.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits8(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
-    /// let (left, right) = input.halve();
-    /// let output = Bits8::union(left, right);
-    /// assert_eq!(input, output);
+    /// let n = Bits6(0b111111_000001_101010);
+    /// assert_eq!(n.extract(0), 0b101010);
+    /// assert_eq!(n.extract(2), 0b111111);
     /// ```
     #[inline]
-    pub fn union(left: Bits16<u128>, right: Bits16<u128>) -> Self {
-        let Bits16(left) = left;
-        let Bits16(right) = right;
-        let left = (left & LEFT_MASKS[2]) >> 8 | left & RIGHT_MASKS[2];
-        let left = (left & LEFT_MASKS[1]) >> 16 | left & RIGHT_MASKS[1];
-        let left = (left & LEFT_MASKS[0]) >> 32 | left & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[2]) >> 8 | right & RIGHT_MASKS[2];
-        let right = (right & LEFT_MASKS[1]) >> 16 | right & RIGHT_MASKS[1];
-        let right = (right & LEFT_MASKS[0]) >> 32 | right & RIGHT_MASKS[0];
-        Self(left << 64 | right)
+    pub const fn extract(self, index: u32) -> u128 {
+        assert!(index < Self::LANES, "element index out of bounds");
+        self.0 >> (index * WIDTH) & Self::MAX_ELEMENT
     }
 
-    /// Squashes half-sized elements into half-sized spaces to the right
-    /// and fills the left half of the number with zeros.
+    /// Replaces the element at `index` with `value`. Must be able to fit.
     ///
-    /// This is the same as `union(0, n)`.
-    #[inline]
-    pub fn squash(self) -> Bits4<u128> {
-        Bits4::union(Self(0), self)
-    }
-
-    #[inline]
-    pub fn count_ones(self) -> u32 {
-        self.0.count_ones()
-    }
-
-    /// Sqishes all the bits to the right in each 16-bit segment.
+    /// Panics if `index` is not less than `LANES`.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits8(0x0101_FFFF);
-    /// let out = Bits16(0x0003_FFFF);
-    /// assert_eq!(input.pack_ones(), out, "got {:08X} expected {:08X}", input.pack_ones().0, out.0);
-    ///
-    /// let input = Bits8(0x0000_FFFF_0F0F_00FF);
-    /// let out = Bits16(0x0000_FFFF_00FF_00FF);
-    /// assert_eq!(input.pack_ones(), out, "got {:016X} expected {:016X}", input.pack_ones().0, out.0);
+    /// let n = Bits6(0b111111_000001_101010);
+    /// assert_eq!(n.insert(1, 0b110011), Bits6(0b111111_110011_101010));
+    /// assert_eq!(Bits6(0).insert(20, 0x3F), Bits6(0b111111 << 120));
     /// ```
     #[inline]
-    pub fn pack_ones(self) -> Bits16<u128> {
-        let Self(x) = self;
-        let x: u16x8 = unsafe { core::mem::transmute(x) };
-        let counted = x.count_ones();
-        // The XOR must be done because there is an issue where shifting in
-        // SIMD shifts by n % LANE_WIDTH, so when we shift by LANE_WIDTH it
-        // actually shifts by 0, so to counteract that we have to take that
-        // 1 bit that is set when LANE_WIDTH is the count and XOR it with
-        // the 1 that is in the 0th bit index to turn the register to 0
-        // so that when we subtract from it we get all 1s.
-        let x = (u16x8::from([1; 8]) << (counted % 16) ^ counted >> 4) - 1;
-        Bits16(unsafe { core::mem::transmute(x) })
+    pub const fn insert(self, index: u32, value: u128) -> Self {
+        assert!(index < Self::LANES, "element index out of bounds");
+        debug_assert!(value <= Self::MAX_ELEMENT, "element does not fit");
+        let shift = index * WIDTH;
+        let lane = Self::MAX_ELEMENT;
+        Self(self.0 & !(lane << shift) | (value & lane) << shift)
     }
 
     #[inline]
-    pub fn sum_weight(self) -> u128 {
-        self.sum_weight2()
-            .sum_weight2()
-            .sum_weight2()
-            .sum_weight2()
-            .0
+    pub const fn count_ones(self) -> u32 {
+        self.0.count_ones()
     }
 
+    /// Converts each element from a thermometer code (see `pack_ones`) to binary.
     #[inline]
-    pub fn sum_weight2(self) -> Bits16<u128> {
-        let (left, right) = self.split();
-        left + right
+    pub const fn thermometer_to_binary(self) -> Self {
+        let () = Self::POWER_OF_TWO;
+        // Counting the bits of a thermometer code gives its value.
+        Self(sum_lanes(self.0, 1, WIDTH))
     }
 
-    /// This computes the minimum hamming weight distance from hamming weights.
+    /// Converts each element from binary to a thermometer code, which sets
+    /// as many bits on the right as the value of the element.
+    ///
+    /// Every element must be no more than `WIDTH`.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let bits = 8;
-    /// for a in 0u128..=bits as u128 {
-    ///     for b in 0u128..=bits as u128 {
-    ///         let aa = Bits8(a | a << bits);
-    ///         let bb = Bits8(b | b << bits);
-    ///         let out = aa.minhwd(bb);
-    ///         let diff = (a as i128 - b as i128).abs() as u128;
-    ///         let expected = Bits8(diff | diff << bits);
-    ///         assert_eq!(out, expected, "got hamming distances {:016b} expected {:016b} ({:08b}, {:08b})", out.0, expected.0, a, b);
-    ///     }
-    /// }
+    /// let binary = Bits4(0x4321_0000);
+    /// let thermometer = Bits4(0xF731_0000);
+    /// assert_eq!(binary.binary_to_thermometer(), thermometer);
+    /// assert_eq!(thermometer.thermometer_to_binary(), binary);
     /// ```
     #[inline]
-    pub fn minhwd(self, other: Self) -> Self {
-        let Self(a) = self;
-        let Self(b) = other;
-        // Compute a + !b for each substring.
-        let m = a + (b ^ WEIGHT_MASK8);
-        // Get the MSB of the weight.
-        let high = m & WEIGHT_MSB8;
-        // If the MSB is not set, we need to add 1 (because -n = ~n + 1).
-        let offset = (high ^ WEIGHT_MSB8) >> 3;
-        // If the MSB is set, we need to flip all the bits.
-        let flips = high | high >> 1;
-        let flips = flips | flips >> 2;
-        // The order we apply the offset and flips in is irrelevant because
-        // only one of the operations will have an effect anyways. We need
-        // to mask out the higher bit at the end because it shouldnt be set.
-        Self(((m ^ flips) + offset) & WEIGHT_MASK8)
-    }
-
-    #[inline]
-    pub fn split(self) -> (Bits16<u128>, Bits16<u128>) {
-        let Self(n) = self;
-        (Bits16((n & LEFT_MASKS[3]) >> 8), Bits16(n & RIGHT_MASKS[3]))
+    pub const fn binary_to_thermometer(self) -> Self {
+        Self(thermometer(self.0, WIDTH))
     }
 
-    /// Takes the left and right sides and spreads them out
-    /// so that the bits in each element are spread out into twice
-    /// the amount of space.
+    /// Converts each element from binary to a one-hot code, which only sets
+    /// the bit at the index of the value of the element.
+    ///
+    /// Every element must be less than `WIDTH`. A `Bits1` element can only hold
+    /// the one-hot code of `0`, so this flips every bit.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits8(0xABCD << 64 | 0xDEAD);
-    /// let (left, right) = input.halve();
-    /// assert_eq!(left, Bits16(0x00AB_00CD));
-    /// assert_eq!(right, Bits16(0x00DE_00AD));
+    /// let binary = Bits4(0x3210_3210_3210_3210_3210_3210_3210_3210);
+    /// let one_hot = Bits4(0x8421_8421_8421_8421_8421_8421_8421_8421);
+    /// assert_eq!(binary.binary_to_one_hot(), one_hot);
+    /// assert_eq!(one_hot.one_hot_to_binary(), binary);
     /// ```
     #[inline]
-    pub fn halve(self) -> (Bits16<u128>, Bits16<u128>) {
-        let Self(n) = self;
-        let left = (n & LEFT_MASKS[0]) >> 64;
-        let left = (left & LEFT_MASKS[1]) << 32 | left & RIGHT_MASKS[1];
-        let left = (left & LEFT_MASKS[2]) << 16 | left & RIGHT_MASKS[2];
-        let left = (left & LEFT_MASKS[3]) << 8 | left & RIGHT_MASKS[3];
-        let right = n & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[1]) << 32 | right & RIGHT_MASKS[1];
-        let right = (right & LEFT_MASKS[2]) << 16 | right & RIGHT_MASKS[2];
-        let right = (right & LEFT_MASKS[3]) << 8 | right & RIGHT_MASKS[3];
-        (Bits16(left), Bits16(right))
-    }
-}
-
-/// Note that you are responsible for dealing with overflow.
-/// Try to avoid overflow or use the split() method to add in two halves.
-/// You can use split() on the result to get the carry bits.
-impl Add for Bits8<u128> {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self {
-        Self(self.0 + rhs.0)
+    pub const fn binary_to_one_hot(self) -> Self {
+        if WIDTH == 1 {
+            Self(!self.0)
+        } else {
+            // Adding one to a thermometer code carries into the bit above it.
+            Self(self.binary_to_thermometer().0 + ones(WIDTH))
+        }
     }
-}
-
-impl BitAnd<u128> for Bits8<u128> {
-    type Output = Self;
 
-    #[inline]
-    fn bitand(self, rhs: u128) -> Self {
-        Self(self.0 & rhs)
-    }
-}
-
-impl Shr<u32> for Bits8<u128> {
-    type Output = Self;
-
-    #[inline]
-    fn shr(self, rhs: u32) -> Self {
-        Self(self.0 >> rhs)
-    }
-}
-
-impl Bits16<u128> {
-    /// Spread a single value out to each element. Must be able to fit.
+    /// Converts each element from a one-hot code to binary.
     ///
-    /// ```
-    /// use swar::*;
-    ///
-    /// assert_eq!(Bits16::from_element(0xFEED), Bits16(0xFEED_FEED_FEED_FEED_FEED_FEED_FEED_FEED));
-    /// ```
+    /// Every element must have exactly one bit set.
     #[inline]
-    pub fn from_element(e: u128) -> Self {
-        // We can do this in log2(bits) time by doubling the sequence.
-        let n5 = e | e << 16;
-        let n6 = n5 | n5 << 32;
-        let n7 = n6 | n6 << 64;
-        Self(n7)
+    pub const fn one_hot_to_binary(self) -> Self {
+        if WIDTH == 1 {
+            Self(!self.0)
+        } else {
+            // Subtracting one from a one-hot code gives a thermometer code of the same value.
+            Self(self.0 - ones(WIDTH)).thermometer_to_binary()
+        }
     }
 
-    /// If any bit is set in each element, sets the element to 1, else 0.
+    /// Adds up every element.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits16(0x8000_0000u128);
-    /// let output = Bits16(0x0001_0000u128);
-    /// assert_eq!(input.any(), output);
+    /// assert_eq!(Bits16(0x0003_0002_0001).sum_weight(), 6);
     /// ```
     #[inline]
-    pub fn any(self) -> Self {
-        let Bits8(x) = Bits8(self.0).any();
-        Self((x & LEFT_MASKS[3]) >> 8 | x & RIGHT_MASKS[3])
+    pub const fn sum_weight(self) -> u128 {
+        let () = Self::POWER_OF_TWO;
+        sum_lanes(self.0, WIDTH, 128)
     }
 
-    /// Takes two inputs that have half-sized elements and compresses them
-    /// into half the space and puts them in the left and right sides of this.
+    /// This computes the minimum hamming weight distance from hamming weights.
+    ///
+    /// For a `Bits1`, this is the same as computing the hamming weight from the
+    /// original number and is a simple XOR.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits16(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
-    /// let (left, right) = input.halve();
-    /// let output = Bits16::union(left, right);
-    /// assert_eq!(input, output);
-    /// ```
-    #[inline]
-    pub fn union(left: Bits32<u128>, right: Bits32<u128>) -> Self {
-        let Bits32(left) = left;
-        let Bits32(right) = right;
-        let left = (left & LEFT_MASKS[1]) >> 16 | left & RIGHT_MASKS[1];
-        let left = (left & LEFT_MASKS[0]) >> 32 | left & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[1]) >> 16 | right & RIGHT_MASKS[1];
-        let right = (right & LEFT_MASKS[0]) >> 32 | right & RIGHT_MASKS[0];
-        Self(left << 64 | right)
-    }
-
-    /// Squashes half-sized elements into half-sized spaces to the right
-    /// and fills the left half of the number with zeros.
+    /// // All combinations of inputs 0-2 (hamming weights)
+    /// let a = Bits2(0b00_01_10_00_01_10_00_01_10u128);
+    /// let b = Bits2(0b00_00_00_01_01_01_10_10_10u128);
+    /// // Expected output weights
+    /// let e = Bits2(0b00_01_10_01_00_01_10_01_00u128);
+    /// assert_eq!(a.minhwd(b), e);
     ///
-    /// This is the same as `union(0, n)`.
-    #[inline]
-    pub fn squash(self) -> Bits8<u128> {
-        Bits8::union(Self(0), self)
-    }
-
+    /// let a = Bits8(0x08_00_03);
+    /// let b = Bits8(0x00_08_05);
+    /// assert_eq!(a.minhwd(b), Bits8(0x08_08_02));
+    /// ```
     #[inline]
-    pub fn count_ones(self) -> u32 {
-        self.0.count_ones()
+    pub const fn minhwd(self, other: Self) -> Self {
+        let () = Self::POWER_OF_TWO;
+        let Self(a) = self;
+        let Self(b) = other;
+        match WIDTH {
+            1 => Self(a ^ b),
+            2 => {
+                // I worked out the Karnaugh map and got the following:
+                // High:
+                // |0|0|x|1|
+                // |0|0|x|0|
+                // |x|x|x|x|
+                // |1|0|x|0|
+                // Low:
+                // |0|1|x|0|
+                // |1|0|x|1|
+                // |x|x|x|x|
+                // |0|1|x|0|
+                // I reduced these maps to the following computation.
+                // high = B1 & !A1 & !A0 | A1 & !B1 & !B0
+                // low = !A0 & B0 | A0 & !B0
+                // Please send PRs if you can improve this.
+                let low = RIGHT_MASKS[6] & (a ^ b);
+                let high = LEFT_MASKS[6] & (b & !a & !a << 1 | a & !b & !b << 1);
+                Self(low | high)
+            }
+            _ => {
+                // The weights are too small for the carry out of the weight bits to
+                // reach the next element past 2 bits, so this works for the rest.
+                let mask = weight_mask(WIDTH);
+                let msb = weight_msb(WIDTH);
+                let log2 = WIDTH.trailing_zeros();
+                // Compute ABC + !DEF.
+                let m = a + (b ^ mask);
+                // Get the MSB of the weight.
+                let high = m & msb;
+                // If the MSB is not set, we need to add 1 (because -n = ~n + 1).
+                let offset = (high ^ msb) >> log2;
+                // If the MSB is set, we need to flip all the bits.
+                let mut flips = high;
+                let mut i = 1;
+                while i <= log2 {
+                    flips |= high >> i;
+                    i += 1;
+                }
+                // The order we apply the offset and flips in is irrelevant because
+                // only one of the operations will have an effect anyways. We need
+                // to mask out the higher bit at the end because it shouldnt be set.
+                Self(((m ^ flips) + offset) & mask)
+            }
+        }
     }
 
-    /// Sqishes all the bits to the right in each 32-bit segment.
+    /// This computes the maximum hamming weight distance from hamming weights.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits16(0xFFFF_0000);
-    /// let out = Bits32(0x0000_FFFF);
-    /// assert_eq!(input.pack_ones(), out, "got {:08X} expected {:08X}", input.pack_ones().0, out.0);
+    /// // All combinations of inputs 0-2 (hamming weights)
+    /// let a = Bits2(0b00_01_10_00_01_10_00_01_10u128);
+    /// let b = Bits2(0b00_00_00_01_01_01_10_10_10u128);
+    /// // Expected output weights
+    /// let e = Bits2(0b00_01_10_01_10_01_10_01_00u128);
+    /// assert_eq!(a.maxhwd(b), e);
     ///
-    /// let input = Bits16(0x00FF_007F);
-    /// let out = Bits32(0x0000_7FFF);
-    /// assert_eq!(input.pack_ones(), out, "got {:08X} expected {:08X}", input.pack_ones().0, out.0);
+    /// assert_eq!(Bits128(100).maxhwd(Bits128(100)), Bits128(56));
+    /// assert_eq!(Bits128(3).maxhwd(Bits128(5)), Bits128(8));
     /// ```
     #[inline]
-    pub fn pack_ones(self) -> Bits32<u128> {
-        let Self(x) = self;
-        let x: u32x4 = unsafe { core::mem::transmute(x) };
-        let counted = x.count_ones();
-        // The XOR must be done because there is an issue where shifting in
-        // SIMD shifts by n % LANE_WIDTH, so when we shift by LANE_WIDTH it
-        // actually shifts by 0, so to counteract that we have to take that
-        // 1 bit that is set when LANE_WIDTH is the count and XOR it with
-        // the 1 that is in the 0th bit index to turn the register to 0
-        // so that when we subtract from it we get all 1s.
-        let x = (u32x4::from([1; 4]) << (counted % 32) ^ counted >> 5) - 1;
-        Bits32(unsafe { core::mem::transmute(x) })
+    pub const fn maxhwd(self, other: Self) -> Self {
+        let () = Self::POWER_OF_TWO;
+        let Self(a) = self;
+        let Self(b) = other;
+        match WIDTH {
+            1 => Self(a ^ b),
+            2 => {
+                // This is the same as `minhwd` except that if the input is
+                // `1` and `1` the bits could be in different spots so the max
+                // is `2`.
+                let low = RIGHT_MASKS[6] & (a ^ b);
+                let high = LEFT_MASKS[6] & (b & !a & !a << 1 | a & !b & !b << 1 | a << 1 & b << 1);
+                Self(low | high)
+            }
+            _ => {
+                // The set bits must overlap by at least `a + b - WIDTH`, so the distance
+                // is at most `WIDTH - |a - (WIDTH - b)|`. No lane can borrow because every
+                // weight is at most `WIDTH`.
+                let n = Self::from_element(WIDTH as u128).0;
+                let Self(d) = self.minhwd(Self(n - b));
+                Self(n - d)
+            }
+        }
     }
+}
 
-    #[inline]
-    pub fn sum_weight(self) -> u128 {
-        self.sum_weight2().sum_weight2().sum_weight2().0
-    }
+/// Implements the routines that go between `Bits<$half>` and `Bits<$width>`.
+macro_rules! impl_double {
+    ($half:literal, $width:literal) => {
+        impl Bits<$half, u128> {
+            /// Takes two inputs that have half-sized elements and compresses them
+            /// into half the space and puts them in the left and right sides of this.
+            #[inline]
+            pub const fn union(left: Bits<$width, u128>, right: Bits<$width, u128>) -> Self {
+                Self(compress(left.0, $half) << 64 | compress(right.0, $half))
+            }
+
+            #[doc = concat!("Sqishes all the bits to the right in each ", stringify!($width), "-bit segment without SIMD.")]
+            ///
+            /// This counts the bits in each segment with `sum_weight2` and then builds
+            /// the result from the counts. It is what `pack_ones` uses unless the `simd`
+            /// feature is enabled.
+            #[inline]
+            pub const fn pack_ones_swar(self) -> Bits<$width, u128> {
+                Bits(thermometer(sum_lanes(self.0, 1, $width), $width))
+            }
+
+            #[inline]
+            pub const fn sum_weight2(self) -> Bits<$width, u128> {
+                let (left, right) = self.split();
+                Bits(left.0 + right.0)
+            }
+
+            #[inline]
+            pub const fn split(self) -> (Bits<$width, u128>, Bits<$width, u128>) {
+                let Self(n) = self;
+                (
+                    Bits((n & left_mask($width)) >> $half),
+                    Bits(n & right_mask($width)),
+                )
+            }
+
+            /// Takes the left and right sides and spreads them out
+            /// so that the bits in each element are spread out into twice
+            /// the amount of space.
+            #[inline]
+            pub const fn halve(self) -> (Bits<$width, u128>, Bits<$width, u128>) {
+                let Self(n) = self;
+                (
+                    Bits(spread(n >> 64, $half)),
+                    Bits(spread(n & RIGHT_MASKS[0], $half)),
+                )
+            }
+        }
+
+        impl Bits<$width, u128> {
+            /// Squashes half-sized elements into half-sized spaces to the right
+            /// and fills the left half of the number with zeros.
+            ///
+            /// This is the same as `union(0, n)`.
+            #[inline]
+            pub const fn squash(self) -> Bits<$half, u128> {
+                Bits::<$half, u128>::union(Self(0), self)
+            }
+        }
+    };
+}
 
-    #[inline]
-    pub fn sum_weight2(self) -> Bits32<u128> {
-        let (left, right) = self.split();
-        left + right
-    }
+impl_double!(1, 2);
+impl_double!(2, 4);
+impl_double!(4, 8);
+impl_double!(8, 16);
+impl_double!(16, 32);
+impl_double!(32, 64);
+impl_double!(64, 128);
 
-    /// This computes the minimum hamming weight distance from hamming weights.
+impl Bits1<u128> {
+    /// Sqishes all the bits to the right in each 2-bit segment.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let bits = 16;
-    /// for a in 0u128..=bits as u128 {
-    ///     for b in 0u128..=bits as u128 {
-    ///         let aa = Bits16(a | a << bits);
-    ///         let bb = Bits16(b | b << bits);
-    ///         let out = aa.minhwd(bb);
-    ///         let diff = (a as i128 - b as i128).abs() as u128;
-    ///         let expected = Bits16(diff | diff << bits);
-    ///         assert_eq!(out, expected);
-    ///     }
-    /// }
+    /// let input = Bits1(0b00_01_10_11);
+    /// let out = Bits2(0b00_01_01_11);
+    /// assert_eq!(input.pack_ones(), out);
     /// ```
     #[inline]
-    pub fn minhwd(self, other: Self) -> Self {
-        let Self(a) = self;
-        let Self(b) = other;
-        // Compute a + !b for each substring.
-        let m = a + (b ^ WEIGHT_MASK16);
-        // Get the MSB of the weight.
-        let high = m & WEIGHT_MSB16;
-        // If the MSB is not set, we need to add 1 (because -n = ~n + 1).
-        let offset = (high ^ WEIGHT_MSB16) >> 4;
-        // If the MSB is set, we need to flip all the bits.
-        let flips = high | high >> 1;
-        let flips = flips | flips >> 2 | high >> 4;
-        // The order we apply the offset and flips in is irrelevant because
-        // only one of the operations will have an effect anyways. We need
-        // to mask out the higher bit at the end because it shouldnt be set.
-        Self(((m ^ flips) + offset) & WEIGHT_MASK16)
-    }
-
-    #[inline]
-    pub fn split(self) -> (Bits32<u128>, Bits32<u128>) {
-        let Self(n) = self;
-        (
-            Bits32((n & LEFT_MASKS[2]) >> 16),
-            Bits32(n & RIGHT_MASKS[2]),
-        )
+    pub const fn pack_ones(self) -> Bits2<u128> {
+        let Self(x) = self;
+        let lower = (x & LEFT_MASKS[6]) >> 1 | x & RIGHT_MASKS[6];
+        let upper = x & (x & RIGHT_MASKS[6]) << 1;
+        Bits2(lower | upper)
     }
+}
 
-    /// Takes the left and right sides and spreads them out
-    /// so that the bits in each element are spread out into twice
-    /// the amount of space.
+impl Bits2<u128> {
+    /// Sqishes all the bits to the right in each 4-bit segment.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits16(0x0BAD_CAFE << 64 | 0xDEAD_BEEF);
-    /// let (left, right) = input.halve();
-    /// assert_eq!(left, Bits32(0x0000_0BAD_0000_CAFE));
-    /// assert_eq!(right, Bits32(0x0000_DEAD_0000_BEEF));
+    /// let input = Bits2(0b11_01_01_00);
+    /// let out = Bits4(0b0111_0001);
+    /// assert_eq!(input.pack_ones(), out);
     /// ```
     #[inline]
-    pub fn halve(self) -> (Bits32<u128>, Bits32<u128>) {
-        let Self(n) = self;
-        let left = (n & LEFT_MASKS[0]) >> 64;
-        let left = (left & LEFT_MASKS[1]) << 32 | left & RIGHT_MASKS[1];
-        let left = (left & LEFT_MASKS[2]) << 16 | left & RIGHT_MASKS[2];
-        let right = n & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[1]) << 32 | right & RIGHT_MASKS[1];
-        let right = (right & LEFT_MASKS[2]) << 16 | right & RIGHT_MASKS[2];
-        (Bits32(left), Bits32(right))
+    pub fn pack_ones(self) -> Bits4<u128> {
+        #[cfg(feature = "simd")]
+        {
+            let Self(x) = self;
+            let x = u8x16::from_array(x.to_ne_bytes());
+            let left = x & u8x16::splat(0xF0);
+            let right = x & u8x16::splat(0x0F);
+            let left_count = left.count_ones();
+            let right_count = right.count_ones();
+            let one = u8x16::splat(1);
+            let left_out = (one << left_count) - one;
+            let right_out = (one << right_count) - one;
+            let x = left_out << u8x16::splat(4) | right_out;
+            Bits4(u128::from_ne_bytes(x.to_array()))
+        }
+        #[cfg(not(feature = "simd"))]
+        {
+            self.pack_ones_swar()
+        }
     }
 }
 
-/// Note that you are responsible for dealing with overflow.
-/// Try to avoid overflow or use the split() method to add in two halves.
-/// You can use split() on the result to get the carry bits.
-impl Add for Bits16<u128> {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self {
-        Self(self.0 + rhs.0)
-    }
+/// Implements `pack_ones` for `Bits<$half>` with `$simd` lanes of `$width` bits.
+macro_rules! impl_pack_ones {
+    ($half:literal, $width:literal, $simd:ident) => {
+        impl Bits<$half, u128> {
+            #[doc = concat!("Sqishes all the bits to the right in each ", stringify!($width), "-bit segment.")]
+            #[inline]
+            pub fn pack_ones(self) -> Bits<$width, u128> {
+                #[cfg(feature = "simd")]
+                {
+                    let Self(x) = self;
+                    let x = $simd::from_ne_bytes(u8x16::from_array(x.to_ne_bytes()));
+                    let counted = x.count_ones();
+                    // The XOR must be done because shifting in SIMD shifts by
+                    // n % LANE_WIDTH, so when we shift by LANE_WIDTH it actually
+                    // shifts by 0, so to counteract that we have to take that
+                    // 1 bit that is set when LANE_WIDTH is the count and XOR it with
+                    // the 1 that is in the 0th bit index to turn the register to 0
+                    // so that when we subtract from it we get all 1s.
+                    let one = $simd::splat(1);
+                    let x = (one << (counted % $simd::splat($width))
+                        ^ counted >> $simd::splat(($width as u32).trailing_zeros() as _))
+                        - one;
+                    Bits(u128::from_ne_bytes(x.to_ne_bytes().to_array()))
+                }
+                #[cfg(not(feature = "simd"))]
+                {
+                    self.pack_ones_swar()
+                }
+            }
+        }
+    };
 }
 
-impl BitAnd<u128> for Bits16<u128> {
-    type Output = Self;
-
-    #[inline]
-    fn bitand(self, rhs: u128) -> Self {
-        Self(self.0 & rhs)
-    }
+impl_pack_ones!(4, 8, u8x16);
+impl_pack_ones!(8, 16, u16x8);
+impl_pack_ones!(16, 32, u32x4);
+impl_pack_ones!(32, 64, u64x2);
+
+macro_rules! impl_sad {
+    ($($width:literal)*) => {
+        $(
+            impl Bits<$width, u128> {
+                /// Adds up the absolute differences of the elements, which is the
+                /// sum of absolute differences (SAD) used to compare image blocks.
+                ///
+                /// The differences are taken in every element at once with
+                /// `abs_diff` and then added up with `sum_weight`.
+                ///
+                /// ```
+                /// use swar::*;
+                ///
+                #[doc = concat!("let a = Bits", stringify!($width), "::from_element(3);")]
+                #[doc = concat!("let b = Bits", stringify!($width), "::from_element(5).insert(0, 0);")]
+                #[doc = concat!("assert_eq!(a.sad(b), 2 * (Bits", stringify!($width), "::<u128>::LANES - 1) + 3);")]
+                /// ```
+                #[inline]
+                pub const fn sad(self, other: Self) -> u32 {
+                    self.abs_diff(other).sum_weight() as u32
+                }
+            }
+        )*
+    };
 }
 
-impl Shr<u32> for Bits16<u128> {
-    type Output = Self;
-
-    #[inline]
-    fn shr(self, rhs: u32) -> Self {
-        Self(self.0 >> rhs)
-    }
-}
+impl_sad!(8 16);
 
-impl Bits32<u128> {
-    /// Spread a single value out to each element. Must be able to fit.
+impl Bits8<u128> {
+    /// Returns `true` if any byte is zero.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// assert_eq!(Bits32::from_element(0xFEED_FACE), Bits32(0xFEED_FACE_FEED_FACE_FEED_FACE_FEED_FACE));
+    /// assert!(Bits8(u128::from_le_bytes(*b"hello\0world!!!!!")).has_zero_lane());
+    /// assert!(!Bits8(u128::from_le_bytes(*b"hello, world!!!!")).has_zero_lane());
     /// ```
     #[inline]
-    pub fn from_element(e: u128) -> Self {
-        // We can do this in log2(bits) time by doubling the sequence.
-        let n6 = e | e << 32;
-        let n7 = n6 | n6 << 64;
-        Self(n7)
+    pub const fn has_zero_lane(self) -> bool {
+        // Subtracting one from a zero byte borrows through its sign bit, which is
+        // only kept if the sign bit wasn't already set. Bytes above a zero byte can
+        // borrow too, but only once there is a zero byte to find.
+        self.0.wrapping_sub(ONES8) & !self.0 & SIGNS8 != 0
     }
 
-    /// If any bit is set in each element, sets the element to 1, else 0.
+    /// Sets the sign bit of every byte that is zero and clears everything else.
+    ///
+    /// Unlike the borrow in `has_zero_lane`, this never flags a byte just because
+    /// the byte below it is zero, so the mask can be counted.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits32(0x8000_0000_0000_0000u128);
-    /// let output = Bits32(0x0000_0001_0000_0000u128);
-    /// assert_eq!(input.any(), output);
+    /// let bytes = Bits8(u128::from_le_bytes(*b"\0\x01\0bcdefghijklmn"));
+    /// assert_eq!(bytes.zero_lane_mask(), Bits8(0x80_00_80));
     /// ```
     #[inline]
-    pub fn any(self) -> Self {
-        let Bits16(x) = Bits16(self.0).any();
-        Self((x & LEFT_MASKS[2]) >> 16 | x & RIGHT_MASKS[2])
+    pub const fn zero_lane_mask(self) -> Self {
+        // Adding all ones below the sign bit of each byte sets the sign bit unless
+        // they were all zero, and it can't carry into the next byte.
+        let low = !SIGNS8;
+        Self(!(((self.0 & low) + low) | self.0) & SIGNS8)
     }
 
-    /// Takes two inputs that have half-sized elements and compresses them
-    /// into half the space and puts them in the left and right sides of this.
+    /// Sets the sign bit of every byte equal to `byte` and clears everything else.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits32(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
-    /// let (left, right) = input.halve();
-    /// let output = Bits32::union(left, right);
-    /// assert_eq!(input, output);
+    /// let text = Bits8(u128::from_le_bytes(*b"a,b,,c.........."));
+    /// assert_eq!(text.eq_lane_mask(b',').first_match_index(), Some(1));
+    /// assert_eq!(text.eq_lane_mask(b',').count_ones(), 3);
     /// ```
     #[inline]
-    pub fn union(left: Bits64<u128>, right: Bits64<u128>) -> Self {
-        let Bits64(left) = left;
-        let Bits64(right) = right;
-        let left = (left & LEFT_MASKS[0]) >> 32 | left & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[0]) >> 32 | right & RIGHT_MASKS[0];
-        Self(left << 64 | right)
+    pub const fn eq_lane_mask(self, byte: u8) -> Self {
+        Self(self.0 ^ Self::from_element(byte as u128).0).zero_lane_mask()
     }
 
-    /// Squashes half-sized elements into half-sized spaces to the right
-    /// and fills the left half of the number with zeros.
+    /// Sets the sign bit of every ASCII byte from `lo` to `hi` and clears everything else.
     ///
-    /// This is the same as `union(0, n)`.
-    #[inline]
-    pub fn squash(self) -> Bits16<u128> {
-        Bits16::union(Self(0), self)
-    }
-
-    #[inline]
-    pub fn count_ones(self) -> u32 {
-        self.0.count_ones()
-    }
-
-    /// Sqishes all the bits to the right in each 64-bit segment.
+    /// Both `lo` and `hi` must be ASCII. Bytes that aren't ASCII never match.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits32(0x0000_FFFF);
-    /// let out = Bits64(0x0000_FFFF);
-    /// assert_eq!(input.pack_ones(), out, "got {:08X} expected {:08X}", input.pack_ones().0, out.0);
-    ///
-    /// let input = Bits32(0x00FF_007F);
-    /// let out = Bits64(0x0000_7FFF);
-    /// assert_eq!(input.pack_ones(), out, "got {:08X} expected {:08X}", input.pack_ones().0, out.0);
+    /// let text = Bits8(u128::from_le_bytes(*b"a1b2\xB1Z9 hello!!!"));
+    /// assert_eq!(text.ascii_range_lane_mask(b'0', b'9'), Bits8(0x80_00_00_80_00_80_00));
     /// ```
     #[inline]
-    pub fn pack_ones(self) -> Bits64<u128> {
-        let Self(x) = self;
-        let x: u64x2 = unsafe { core::mem::transmute(x) };
-        let counted = x.count_ones();
-        // The XOR must be done because there is an issue where shifting in
-        // SIMD shifts by n % LANE_WIDTH, so when we shift by LANE_WIDTH it
-        // actually shifts by 0, so to counteract that we have to take that
-        // 1 bit that is set when LANE_WIDTH is the count and XOR it with
-        // the 1 that is in the 0th bit index to turn the register to 0
-        // so that when we subtract from it we get all 1s.
-        let x = (u64x2::from([1; 2]) << (counted % 64) ^ counted >> 6) - 1;
-        Bits64(unsafe { core::mem::transmute(x) })
+    pub const fn ascii_range_lane_mask(self, lo: u8, hi: u8) -> Self {
+        debug_assert!(lo.is_ascii() && hi.is_ascii(), "range must be ASCII");
+        // Without the sign bits, adding `0x80 - lo` reaches the sign bit when a byte is
+        // at least `lo`, and adding `0x7F - hi` reaches it when a byte is above `hi`.
+        // Neither can carry into the next byte.
+        let x = self.0 & !SIGNS8;
+        let at_least_lo = x + Self::from_element(0x80 - lo as u128).0;
+        let above_hi = x + Self::from_element(0x7F - hi as u128).0;
+        Self(at_least_lo & !above_hi & !self.0 & SIGNS8)
     }
 
+    /// Sets the sign bit of every ASCII uppercase letter.
     #[inline]
-    pub fn sum_weight(self) -> u128 {
-        self.sum_weight2().sum_weight2().0
+    pub const fn ascii_uppercase_lane_mask(self) -> Self {
+        self.ascii_range_lane_mask(b'A', b'Z')
     }
 
+    /// Sets the sign bit of every ASCII lowercase letter.
     #[inline]
-    pub fn sum_weight2(self) -> Bits64<u128> {
-        let (left, right) = self.split();
-        left + right
+    pub const fn ascii_lowercase_lane_mask(self) -> Self {
+        self.ascii_range_lane_mask(b'a', b'z')
     }
 
-    /// This computes the minimum hamming weight distance from hamming weights.
+    /// Sets the sign bit of every ASCII letter.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let bits = 32;
-    /// for a in 0u128..=bits as u128 {
-    ///     for b in 0u128..=bits as u128 {
-    ///         let aa = Bits32(a | a << bits);
-    ///         let bb = Bits32(b | b << bits);
-    ///         let out = aa.minhwd(bb);
-    ///         let diff = (a as i128 - b as i128).abs() as u128;
-    ///         let expected = Bits32(diff | diff << bits);
-    ///         assert_eq!(out, expected);
-    ///     }
-    /// }
+    /// let text = Bits8(u128::from_le_bytes(*b"Az@[`{0\xC1........"));
+    /// assert_eq!(text.ascii_alphabetic_lane_mask(), Bits8(0x80_80));
     /// ```
     #[inline]
-    pub fn minhwd(self, other: Self) -> Self {
-        let Self(a) = self;
-        let Self(b) = other;
-        // Compute a + !b for each substring.
-        let m = a + (b ^ WEIGHT_MASK32);
-        // Get the MSB of the weight.
-        let high = m & WEIGHT_MSB32;
-        // If the MSB is not set, we need to add 1 (because -n = ~n + 1).
-        let offset = (high ^ WEIGHT_MSB32) >> 5;
-        // If the MSB is set, we need to flip all the bits.
-        let flips = high | high >> 1;
-        let flips = flips | flips >> 2;
-        let flips = flips | flips >> 2;
-        // The order we apply the offset and flips in is irrelevant because
-        // only one of the operations will have an effect anyways. We need
-        // to mask out the higher bit at the end because it shouldnt be set.
-        Self(((m ^ flips) + offset) & WEIGHT_MASK32)
+    pub const fn ascii_alphabetic_lane_mask(self) -> Self {
+        // Setting the 0x20 bit turns uppercase letters into lowercase letters, and
+        // only maps other bytes onto letters when they are not ASCII.
+        Self(self.0 | Self::from_element(0x20).0).ascii_lowercase_lane_mask()
     }
 
+    /// Sets the sign bit of every ASCII digit.
     #[inline]
-    pub fn split(self) -> (Bits64<u128>, Bits64<u128>) {
-        let Self(n) = self;
-        (
-            Bits64((n & LEFT_MASKS[1]) >> 32),
-            Bits64(n & RIGHT_MASKS[1]),
-        )
+    pub const fn ascii_digit_lane_mask(self) -> Self {
+        self.ascii_range_lane_mask(b'0', b'9')
     }
 
-    /// Takes the left and right sides and spreads them out
-    /// so that the bits in each element are spread out into twice
-    /// the amount of space.
+    /// Sets the sign bit of every byte that is ASCII whitespace, which is the same
+    /// set of bytes as `u8::is_ascii_whitespace`.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits32(0x0BAD_CAFE << 64 | 0xDEAD_BEEF);
-    /// let (left, right) = input.halve();
-    /// assert_eq!(left, Bits64(0x0BAD_CAFE));
-    /// assert_eq!(right, Bits64(0xDEAD_BEEF));
+    /// let text = Bits8(u128::from_le_bytes(*b" \t\n\x0B\x0C\rab........"));
+    /// assert_eq!(text.ascii_whitespace_lane_mask(), Bits8(0x80_80_00_80_80_80));
     /// ```
     #[inline]
-    pub fn halve(self) -> (Bits64<u128>, Bits64<u128>) {
-        let Self(n) = self;
-        let left = (n & LEFT_MASKS[0]) >> 64;
-        let left = (left & LEFT_MASKS[1]) << 32 | left & RIGHT_MASKS[1];
-        let right = n & RIGHT_MASKS[0];
-        let right = (right & LEFT_MASKS[1]) << 32 | right & RIGHT_MASKS[1];
-        (Bits64(left), Bits64(right))
-    }
-}
-
-/// Note that you are responsible for dealing with overflow.
-/// Try to avoid overflow or use the split() method to add in two halves.
-/// You can use split() on the result to get the carry bits.
-impl Add for Bits32<u128> {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self {
-        Self(self.0 + rhs.0)
-    }
-}
-
-impl BitAnd<u128> for Bits32<u128> {
-    type Output = Self;
-
-    #[inline]
-    fn bitand(self, rhs: u128) -> Self {
-        Self(self.0 & rhs)
-    }
-}
-
-impl Shr<u32> for Bits32<u128> {
-    type Output = Self;
-
-    #[inline]
-    fn shr(self, rhs: u32) -> Self {
-        Self(self.0 >> rhs)
+    pub const fn ascii_whitespace_lane_mask(self) -> Self {
+        // This is '\t' and '\n', then '\x0C' and '\r', skipping the vertical tab.
+        Self(
+            self.ascii_range_lane_mask(b'\t', b'\n').0
+                | self.ascii_range_lane_mask(b'\x0C', b'\r').0
+                | self.eq_lane_mask(b' ').0,
+        )
     }
-}
 
-impl Bits64<u128> {
-    /// Spread a single value out to each element. Must be able to fit.
+    /// Converts every ASCII uppercase letter to lowercase and leaves everything else.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// assert_eq!(Bits64::from_element(0xFEED_FACE_CAFE_BEEF), Bits64(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF));
+    /// let text = Bits8(u128::from_le_bytes(*b"Hello, WORLD! @["));
+    /// assert_eq!(text.to_ascii_lowercase(), Bits8(u128::from_le_bytes(*b"hello, world! @[")));
     /// ```
     #[inline]
-    pub fn from_element(e: u128) -> Self {
-        // We can do this in log2(bits) time by doubling the sequence.
-        let n7 = e | e << 64;
-        Self(n7)
+    pub const fn to_ascii_lowercase(self) -> Self {
+        // Moving the sign bit of the mask down to 0x20 gives the bit to set.
+        Self(self.0 | self.ascii_uppercase_lane_mask().0 >> 2)
     }
 
-    /// If any bit is set in each element, sets the element to 1, else 0.
+    /// Converts every ASCII lowercase letter to uppercase and leaves everything else.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits64(0x8000_0000_0000_0000_0000_0000_0000_0000u128);
-    /// let output = Bits64(0x0000_0000_0000_0001_0000_0000_0000_0000u128);
-    /// assert_eq!(input.any(), output);
+    /// let text = Bits8(u128::from_le_bytes(*b"Hello, world! `{"));
+    /// assert_eq!(text.to_ascii_uppercase(), Bits8(u128::from_le_bytes(*b"HELLO, WORLD! `{")));
     /// ```
     #[inline]
-    pub fn any(self) -> Self {
-        let Bits32(x) = Bits32(self.0).any();
-        Self((x & LEFT_MASKS[1]) >> 32 | x & RIGHT_MASKS[1])
+    pub const fn to_ascii_uppercase(self) -> Self {
+        Self(self.0 ^ self.ascii_lowercase_lane_mask().0 >> 2)
     }
 
-    /// Takes two inputs that have half-sized elements and compresses them
-    /// into half the space and puts them in the left and right sides of this.
+    /// Gets the index of the lowest byte with any bit set, such as the first
+    /// match in a mask from `zero_lane_mask` or `eq_lane_mask`.
+    ///
+    /// The lowest byte is the first one in memory when loaded with `from_le_bytes`.
     ///
     /// ```
     /// use swar::*;
     ///
-    /// let input = Bits64(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
-    /// let (left, right) = input.halve();
-    /// let output = Bits64::union(left, right);
-    /// assert_eq!(input, output);
+    /// assert_eq!(Bits8(0x80_00_00u128).first_match_index(), Some(2));
+    /// assert_eq!(Bits8(0u128).first_match_index(), None);
     /// ```
     #[inline]
-    pub fn union(left: Bits128<u128>, right: Bits128<u128>) -> Self {
-        let Bits128(left) = left;
-        let Bits128(right) = right;
-        Self(left << 64 | right)
+    pub const fn first_match_index(self) -> Option<u32> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(self.0.trailing_zeros() / 8)
+        }
     }
+}
 
-    /// Squashes half-sized elements into half-sized spaces to the right
-    /// and fills the left half of the number with zeros.
+impl Bits64<u128> {
+    /// Sqishes all the bits to the right in each 128-bit segment.
     ///
-    /// This is the same as `union(0, n)`.
-    #[inline]
-    pub fn squash(self) -> Bits32<u128> {
-        Bits32::union(Self(0), self)
-    }
-
-    #[inline]
-    pub fn count_ones(self) -> u32 {
-        self.0.count_ones()
-    }
-
-    /// Sqishes all the bits to the right in each 64-bit segment.
+    /// This is the same as `pack_ones_swar`.
     ///
     /// ```
     /// use swar::*;
     ///
     /// let input = Bits64(0x00FF_00FF);
     /// let out = Bits128(0x0000_FFFF);
-    /// assert_eq!(input.pack_ones(), out, "got {:08X} expected {:08X}", input.pack_ones().0, out.0);
-    /// ```
-    #[inline]
-    pub fn pack_ones(self) -> Bits128<u128> {
-        let Self(x) = self;
-        Bits128((1 << x.count_ones()) - 1)
-    }
-
-    #[inline]
-    pub fn sum_weight(self) -> u128 {
-        self.sum_weight2().0
-    }
-
-    #[inline]
-    pub fn sum_weight2(self) -> Bits128<u128> {
-        let (left, right) = self.split();
-        left + right
-    }
-
-    /// This computes the minimum hamming weight distance from hamming weights.
-    ///
-    /// ```
-    /// use swar::*;
-    ///
-    /// let bits = 64;
-    /// for a in 0u128..=bits as u128 {
-    ///     for b in 0u128..=bits as u128 {
-    ///         let aa = Bits64(a | a << bits);
-    ///         let bb = Bits64(b | b << bits);
-    ///         let out = aa.minhwd(bb);
-    ///         let diff = (a as i128 - b as i128).abs() as u128;
-    ///         let expected = Bits64(diff | diff << bits);
-    ///         assert_eq!(out, expected);
-    ///     }
-    /// }
+    /// assert_eq!(input.pack_ones(), out);
+    /// assert_eq!(Bits64(u128::MAX).pack_ones(), Bits128(u128::MAX));
     /// ```
     #[inline]
-    pub fn minhwd(self, other: Self) -> Self {
-        let Self(a) = self;
-        let Self(b) = other;
-        let a_low = a as i32;
-        let a_high = (a >> 64) as i32;
-        let b_low = b as i32;
-        let b_high = (b >> 64) as i32;
-        Self(((a_high - b_high).abs() as u128) << 64 | (a_low - b_low).abs() as u128)
+    pub const fn pack_ones(self) -> Bits128<u128> {
+        self.pack_ones_swar()
     }
+}
 
+impl<const WIDTH: u32> From<u128> for Bits<WIDTH, u128> {
     #[inline]
-    pub fn split(self) -> (Bits128<u128>, Bits128<u128>) {
-        let Self(n) = self;
-        (
-            Bits128((n & LEFT_MASKS[0]) >> 64),
-            Bits128(n & RIGHT_MASKS[0]),
-        )
+    fn from(n: u128) -> Self {
+        Self(n)
     }
+}
 
-    /// Takes the left and right sides and spreads them out
-    /// so that the bits in each element are spread out into twice
-    /// the amount of space.
-    ///
-    /// ```
-    /// use swar::*;
-    ///
-    /// let input = Bits64(0x0BAD_CAFE << 64 | 0xDEAD_BEEF);
-    /// let (left, right) = input.halve();
-    /// assert_eq!(left, Bits128(0x0BAD_CAFE));
-    /// assert_eq!(right, Bits128(0xDEAD_BEEF));
-    /// ```
+impl<const WIDTH: u32> From<Bits<WIDTH, u128>> for u128 {
     #[inline]
-    pub fn halve(self) -> (Bits128<u128>, Bits128<u128>) {
-        let Self(n) = self;
-        let left = (n & LEFT_MASKS[0]) >> 64;
-        let right = n & RIGHT_MASKS[0];
-        (Bits128(left), Bits128(right))
+    fn from(n: Bits<WIDTH, u128>) -> u128 {
+        n.0
     }
 }
 
-/// Note that you are responsible for dealing with overflow.
-/// Try to avoid overflow or use the split() method to add in two halves.
-/// You can use split() on the result to get the carry bits.
-impl Add for Bits64<u128> {
+/// Adds each pair of elements, which must not overflow.
+///
+/// An element that overflows carries into the next one, and debug builds panic
+/// when that happens. Use `wrapping_add`, `overflowing_add` or `checked_add` to
+/// deal with overflow instead.
+impl<const WIDTH: u32> Add for Bits<WIDTH, u128> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self {
+        debug_assert!(
+            self.checked_add(rhs).is_some(),
+            "attempt to add with overflow in an element"
+        );
         Self(self.0 + rhs.0)
     }
 }
 
-impl BitAnd<u128> for Bits64<u128> {
+/// Note that you are responsible for dealing with underflow, which borrows from
+/// the element above. Use `wrapping_sub` to keep the elements apart.
+///
+/// Debug builds panic if any element underflows.
+impl<const WIDTH: u32> Sub for Bits<WIDTH, u128> {
     type Output = Self;
 
-    #[inline]
-    fn bitand(self, rhs: u128) -> Self {
-        Self(self.0 & rhs)
+    fn sub(self, rhs: Self) -> Self {
+        debug_assert!(
+            self.lanes_lt(rhs).0 == 0,
+            "attempt to subtract with overflow in an element"
+        );
+        Self(self.0 - rhs.0)
     }
 }
 
-impl Shr<u32> for Bits64<u128> {
+impl<const WIDTH: u32> BitAnd for Bits<WIDTH, u128> {
     type Output = Self;
 
     #[inline]
-    fn shr(self, rhs: u32) -> Self {
-        Self(self.0 >> rhs)
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
     }
 }
 
-impl Bits128<u128> {
-    /// If any bit is set in each element, sets the element to 1, else 0.
-    ///
-    /// ```
-    /// use swar::*;
-    ///
-    /// let input = Bits128(0x8000_0000_0000_0000_0000_0000_0000_0000u128);
-    /// let output = Bits128(0x0000_0000_0000_0000_0000_0000_0000_0001u128);
-    /// assert_eq!(input.any(), output);
-    ///
-    /// let input = Bits128(0x0000_0000_0000_0000_0000_0000_0000_0000u128);
-    /// let output = Bits128(0x0000_0000_0000_0000_0000_0000_0000_0000u128);
-    /// assert_eq!(input.any(), output);
-    /// ```
-    #[inline]
-    pub fn any(self) -> Self {
-        let Bits64(x) = Bits64(self.0).any();
-        Self((x & LEFT_MASKS[0]) >> 64 | x & RIGHT_MASKS[0])
-    }
+impl<const WIDTH: u32> BitAnd<u128> for Bits<WIDTH, u128> {
+    type Output = Self;
 
-    /// Squashes half-sized elements into half-sized spaces to the right
-    /// and fills the left half of the number with zeros.
-    ///
-    /// This is the same as `union(0, n)`.
     #[inline]
-    pub fn squash(self) -> Bits64<u128> {
-        Bits64::union(Self(0), self)
+    fn bitand(self, rhs: u128) -> Self {
+        Self(self.0 & rhs)
     }
+}
 
-    #[inline]
-    pub fn count_ones(self) -> u32 {
-        self.0.count_ones()
-    }
+impl<const WIDTH: u32> BitOr for Bits<WIDTH, u128> {
+    type Output = Self;
 
     #[inline]
-    pub fn sum_weight(self) -> u128 {
-        self.0
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
     }
+}
+
+impl<const WIDTH: u32> BitXor for Bits<WIDTH, u128> {
+    type Output = Self;
 
     #[inline]
-    pub fn minhwd(self, other: Self) -> Self {
-        Self((self.0 as i32 - other.0 as i32).abs() as u128)
+    fn bitxor(self, rhs: Self) -> Self {
+        Self(self.0 ^ rhs.0)
     }
 }
 
-impl From<Bits128<u128>> for u128 {
+/// The bits above the last whole element stay clear.
+impl<const WIDTH: u32> Not for Bits<WIDTH, u128> {
+    type Output = Self;
+
     #[inline]
-    fn from(n: Bits128<u128>) -> u128 {
-        n.0
+    fn not(self) -> Self {
+        Self(!self.0 & used_mask(WIDTH))
     }
 }
 
-impl BitAnd<u128> for Bits128<u128> {
+/// This shifts the whole number, so bits move between elements.
+/// The bits shifted past the last whole element are dropped.
+impl<const WIDTH: u32> Shl<u32> for Bits<WIDTH, u128> {
     type Output = Self;
 
     #[inline]
-    fn bitand(self, rhs: u128) -> Self {
-        Self(self.0 & rhs)
+    fn shl(self, rhs: u32) -> Self {
+        Self(self.0 << rhs & used_mask(WIDTH))
     }
 }
 
-impl Shr<u32> for Bits128<u128> {
+impl<const WIDTH: u32> Shr<u32> for Bits<WIDTH, u128> {
     type Output = Self;
 
     #[inline]
@@ -1590,10 +1164,23 @@ impl Shr<u32> for Bits128<u128> {
     }
 }
 
-impl Add for Bits128<u128> {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self {
-        Self(self.0 + rhs.0)
-    }
+/// Implements an assigning operator in terms of the operator it assigns the result of.
+macro_rules! impl_assign {
+    ($assign:ident, $assign_fn:ident, $op:ident, $op_fn:ident, $rhs:ty) => {
+        impl<const WIDTH: u32> $assign<$rhs> for Bits<WIDTH, u128> {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: $rhs) {
+                *self = $op::$op_fn(*self, rhs);
+            }
+        }
+    };
 }
+
+impl_assign!(AddAssign, add_assign, Add, add, Self);
+impl_assign!(SubAssign, sub_assign, Sub, sub, Self);
+impl_assign!(BitAndAssign, bitand_assign, BitAnd, bitand, Self);
+impl_assign!(BitAndAssign, bitand_assign, BitAnd, bitand, u128);
+impl_assign!(BitOrAssign, bitor_assign, BitOr, bitor, Self);
+impl_assign!(BitXorAssign, bitxor_assign, BitXor, bitxor, Self);
+impl_assign!(ShlAssign, shl_assign, Shl, shl, u32);
+impl_assign!(ShrAssign, shr_assign, Shr, shr, u32);
@@ -0,0 +1,67 @@
+use crate::*;
+
+pub const ONES8: u64 = crate::u128::ONES8 as u64;
+
+pub const SIGNS8: u64 = crate::u128::SIGNS8 as u64;
+
+impl Bits8<u64> {
+    /// Returns `true` if any byte is zero.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// assert!(Bits8(u64::from_le_bytes(*b"abc\0defg")).has_zero_lane());
+    /// assert!(!Bits8(u64::from_le_bytes(*b"abcdefgh")).has_zero_lane());
+    /// ```
+    #[inline]
+    pub const fn has_zero_lane(self) -> bool {
+        // See `Bits8<u128>::has_zero_lane`.
+        self.0.wrapping_sub(ONES8) & !self.0 & SIGNS8 != 0
+    }
+
+    /// Sets the sign bit of every byte that is zero and clears everything else.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// let bytes = Bits8(u64::from_le_bytes(*b"\0\x01\0bcdef"));
+    /// assert_eq!(bytes.zero_lane_mask(), Bits8(0x80_00_80));
+    /// ```
+    #[inline]
+    pub const fn zero_lane_mask(self) -> Self {
+        // See `Bits8<u128>::zero_lane_mask`.
+        let low = !SIGNS8;
+        Self(!(((self.0 & low) + low) | self.0) & SIGNS8)
+    }
+
+    /// Sets the sign bit of every byte equal to `byte` and clears everything else.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// let text = Bits8(u64::from_le_bytes(*b"key=val\n"));
+    /// assert_eq!(text.eq_lane_mask(b'=').first_match_index(), Some(3));
+    /// ```
+    #[inline]
+    pub const fn eq_lane_mask(self, byte: u8) -> Self {
+        Self(self.0 ^ (ONES8 * byte as u64)).zero_lane_mask()
+    }
+
+    /// Gets the index of the lowest byte with any bit set, such as the first
+    /// match in a mask from `zero_lane_mask` or `eq_lane_mask`.
+    ///
+    /// ```
+    /// use swar::*;
+    ///
+    /// assert_eq!(Bits8(0x80_00_00u64).first_match_index(), Some(2));
+    /// assert_eq!(Bits8(0u64).first_match_index(), None);
+    /// ```
+    #[inline]
+    pub const fn first_match_index(self) -> Option<u32> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(self.0.trailing_zeros() / 8)
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::bytes::{load, words};
+use crate::u128::SIGNS8;
+use crate::*;
+
+/// Returns `true` if every byte is ASCII, checking 16 bytes at a time.
+///
+/// ```
+/// use swar::*;
+///
+/// assert!(is_ascii(b"GET /index.html HTTP/1.1"));
+/// assert!(!is_ascii("GET /caf\u{E9}.html HTTP/1.1".as_bytes()));
+/// ```
+pub fn is_ascii(bytes: &[u8]) -> bool {
+    words(bytes).all(|(_, Bits(word), _)| word & SIGNS8 == 0)
+}
+
+/// Checks that `bytes` is valid UTF-8, 16 bytes at a time.
+///
+/// Words of ASCII are skipped whole. Otherwise the continuation bytes of the word
+/// are found with lane masks, and every sequence that starts in the word is
+/// checked against them before the next word is loaded.
+///
+/// The error has the same `valid_up_to` and `error_len` as the one from
+/// `core::str::from_utf8`.
+///
+/// ```
+/// use swar::*;
+///
+/// assert_eq!(validate_utf8("ASCII, then \u{E9}\u{20AC}\u{1F600}".as_bytes()), Ok(()));
+///
+/// let error = validate_utf8(b"surrogate \xED\xA0\x80").unwrap_err();
+/// assert_eq!((error.valid_up_to(), error.error_len()), (10, Some(1)));
+/// ```
+pub fn validate_utf8(bytes: &[u8]) -> Result<(), Utf8Error> {
+    let mut start = 0;
+    while start < bytes.len() {
+        let (word, _) = load(bytes, start);
+        let Bits(mut signs) = word & SIGNS8;
+        if signs == 0 {
+            start += 16;
+            continue;
+        }
+        // The padding past the end is zero, so it is never a continuation byte.
+        let Bits(continuation) = Bits8(word.0 & Bits8::from_element(0xC0).0).eq_lane_mask(0x80);
+        let mut next = 16;
+        while let Some(first) = Bits8(signs).first_match_index() {
+            let first = first as usize;
+            // A sequence that starts here might not fit in the word, so it is
+            // checked after the next load instead.
+            if first > 12 {
+                next = first;
+                break;
+            }
+            let available = bytes.len() - (start + first);
+            let len = sequence_len(
+                word.0 >> (8 * first),
+                continuation >> (8 * first),
+                available,
+            )
+            .map_err(|error_len| Utf8Error {
+                valid_up_to: start + first,
+                error_len,
+            })?;
+            // Every byte with its sign bit set up to the end of the sequence has
+            // been checked.
+            signs &= u128::MAX.checked_shl(8 * (first + len) as u32).unwrap_or(0);
+        }
+        start += next;
+    }
+    Ok(())
+}
+
+/// Checks the sequence that starts with the first byte of `word`, which isn't
+/// ASCII, and returns its length or else the `error_len` of the error.
+///
+/// `continuation` has the sign bit set for every continuation byte of `word`,
+/// and `available` is how many of the bytes of `word` are in the input.
+#[inline]
+fn sequence_len(word: u128, continuation: u128, available: usize) -> Result<usize, Option<u8>> {
+    // Find how many continuation bytes (0b10xxxxxx) follow the first byte.
+    let following = Bits8(!continuation >> 8 & SIGNS8)
+        .first_match_index()
+        .unwrap_or(15) as usize;
+    let [first, second, ..] = word.to_le_bytes();
+    let len = match first {
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return Err(Some(1)),
+    };
+    // The second byte has a narrower range after some first bytes, which rules
+    // out overlong encodings, surrogates and code points past U+10FFFF.
+    let second_range = match first {
+        0xE0 => 0xA0..=0xBF,
+        0xED => 0x80..=0x9F,
+        0xF0 => 0x90..=0xBF,
+        0xF4 => 0x80..=0x8F,
+        _ => 0x80..=0xBF,
+    };
+    if available < 2 {
+        return Err(None);
+    }
+    if !second_range.contains(&second) {
+        return Err(Some(1));
+    }
+    // Every byte after the second only needs to be a continuation byte.
+    for i in 2..len {
+        if following < i {
+            return Err(if available <= i { None } else { Some(i as u8) });
+        }
+    }
+    Ok(len)
+}
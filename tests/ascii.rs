@@ -0,0 +1,86 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+/// Builds the mask that sets the sign bit of every byte matching `f`.
+fn lane_mask(bytes: [u8; 16], f: impl Fn(&u8) -> bool) -> Bits8<u128> {
+    Bits8(
+        bytes
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| f(byte))
+            .fold(0, |mask, (i, _)| mask | 0x80 << (8 * i)),
+    )
+}
+
+#[test]
+fn ascii_lane_masks_match_u8() {
+    // Every byte shows up in every position.
+    for offset in 0..=255u8 {
+        let mut bytes = [0; 16];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = offset.wrapping_add(i as u8 * 16);
+        }
+        let word = Bits8(u128::from_le_bytes(bytes));
+        assert_eq!(
+            word.ascii_uppercase_lane_mask(),
+            lane_mask(bytes, u8::is_ascii_uppercase)
+        );
+        assert_eq!(
+            word.ascii_lowercase_lane_mask(),
+            lane_mask(bytes, u8::is_ascii_lowercase)
+        );
+        assert_eq!(
+            word.ascii_alphabetic_lane_mask(),
+            lane_mask(bytes, u8::is_ascii_alphabetic)
+        );
+        assert_eq!(
+            word.ascii_digit_lane_mask(),
+            lane_mask(bytes, u8::is_ascii_digit)
+        );
+        assert_eq!(
+            word.ascii_whitespace_lane_mask(),
+            lane_mask(bytes, u8::is_ascii_whitespace)
+        );
+        assert_eq!(
+            word.to_ascii_lowercase().0.to_le_bytes(),
+            bytes.map(|byte| byte.to_ascii_lowercase())
+        );
+        assert_eq!(
+            word.to_ascii_uppercase().0.to_le_bytes(),
+            bytes.map(|byte| byte.to_ascii_uppercase())
+        );
+    }
+}
+
+#[test]
+fn ascii_slices_match_u8() {
+    let mut rng = SmallRng::from_seed([9; 16]);
+    let alphabet = b"aZ09 \t\n\x0B@[`{\x80\xFF";
+    for len in 0..64 {
+        for _ in 0..100 {
+            let mut bytes: Vec<u8> = (0..len)
+                .map(|_| alphabet[rng.gen_range(0, alphabet.len())])
+                .collect();
+            assert_eq!(
+                all_ascii_digits(&bytes),
+                bytes.iter().all(u8::is_ascii_digit)
+            );
+            assert_eq!(
+                all_ascii_alphabetic(&bytes),
+                bytes.iter().all(u8::is_ascii_alphabetic)
+            );
+            assert_eq!(
+                all_ascii_whitespace(&bytes),
+                bytes.iter().all(u8::is_ascii_whitespace)
+            );
+
+            let expected = bytes.to_ascii_uppercase();
+            make_ascii_uppercase(&mut bytes);
+            assert_eq!(bytes, expected);
+            let expected = bytes.to_ascii_lowercase();
+            make_ascii_lowercase(&mut bytes);
+            assert_eq!(bytes, expected);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encodes `bytes` with a table, one 6-bit field at a time.
+fn encode(alphabet: &[u8; 64], bytes: &[u8]) -> Vec<u8> {
+    let mut text = Vec::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0, |n, (i, &byte)| n | (byte as usize) << (16 - 8 * i));
+        for i in 0..4 {
+            text.push(if i <= chunk.len() {
+                alphabet[n >> (18 - 6 * i) & 63]
+            } else {
+                b'='
+            });
+        }
+    }
+    text
+}
+
+#[test]
+fn base64_matches_table() {
+    let mut rng = SmallRng::from_seed([15; 16]);
+    for (base64, alphabet) in [(Base64::Standard, STANDARD), (Base64::UrlSafe, URL_SAFE)] {
+        for len in 0..40 {
+            for _ in 0..100 {
+                let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+                let expected = encode(alphabet, &bytes);
+                let mut text = vec![0; Base64::encoded_len(len)];
+                base64.encode_slice(&bytes, &mut text);
+                assert_eq!(text, expected);
+
+                let mut decoded = vec![0; len];
+                assert_eq!(base64.decode_slice(&text, &mut decoded), Ok(len));
+                assert_eq!(decoded, bytes);
+                let unpadded = text.iter().position(|&byte| byte == b'=');
+                let unpadded = &text[..unpadded.unwrap_or(text.len())];
+                assert_eq!(base64.decode_slice(unpadded, &mut decoded), Ok(len));
+                assert_eq!(decoded, bytes);
+            }
+        }
+    }
+}
+
+#[test]
+fn base64_decode_rejects_bytes_outside_the_alphabet() {
+    for (base64, alphabet) in [(Base64::Standard, STANDARD), (Base64::UrlSafe, URL_SAFE)] {
+        for byte in 0..=255u8 {
+            for index in 0..8 {
+                let mut text = *b"TWFuLi4u";
+                text[index] = byte;
+                match alphabet.iter().position(|&digit| digit == byte) {
+                    Some(field) => {
+                        let shift = 42 - 6 * index;
+                        let n = 0x0000_4D61_6E2E_2E2E & !(63 << shift) | (field as u64) << shift;
+                        assert_eq!(base64.decode_chunk(text), Ok(n));
+                    }
+                    None => assert_eq!(
+                        base64.decode_chunk(text),
+                        Err(InvalidBase64 { index, byte })
+                    ),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn base64_decode_rejects_a_lone_last_digit() {
+    let mut bytes = [0; 8];
+    assert_eq!(
+        Base64::Standard.decode_slice(b"TWFuL", &mut bytes),
+        Err(InvalidBase64 {
+            index: 4,
+            byte: b'L'
+        })
+    );
+    assert_eq!(
+        Base64::Standard.decode_slice(b"TWFu=Li4", &mut bytes),
+        Err(InvalidBase64 {
+            index: 4,
+            byte: b'='
+        })
+    );
+}
+
+#[test]
+fn base64_decode_only_accepts_padding_that_fills_the_last_group() {
+    let mut bytes = [0; 8];
+    for (text, index) in [
+        (&b"QUJD=="[..], 4),
+        (b"QUJD=", 4),
+        (b"=", 0),
+        (b"==", 0),
+        (b"QQ=", 2),
+        (b"QQ===", 2),
+        (b"Q===", 1),
+        (b"QUI==", 3),
+        (b"QQ=A", 2),
+        (b"QUJD====", 4),
+    ] {
+        assert_eq!(
+            Base64::Standard.decode_slice(text, &mut bytes),
+            Err(InvalidBase64 { index, byte: b'=' }),
+            "{:?}",
+            std::str::from_utf8(text)
+        );
+    }
+    assert_eq!(Base64::Standard.decode_slice(b"QQ==", &mut bytes), Ok(1));
+    assert_eq!(Base64::Standard.decode_slice(b"QUI=", &mut bytes), Ok(2));
+    assert_eq!(Base64::Standard.decode_slice(b"", &mut bytes), Ok(0));
+}
+
+#[test]
+fn base64_decode_rejects_unused_bits() {
+    let mut bytes = [0; 16];
+    // "QQ==" and "QUI=" are the canonical encodings of "A" and "AB".
+    assert_eq!(
+        Base64::Standard.decode_slice(b"QR==", &mut bytes),
+        Err(InvalidBase64 {
+            index: 1,
+            byte: b'R'
+        })
+    );
+    assert_eq!(
+        Base64::Standard.decode_slice(b"QUJ", &mut bytes),
+        Err(InvalidBase64 {
+            index: 2,
+            byte: b'J'
+        })
+    );
+    assert_eq!(
+        Base64::Standard.decode_slice(b"TWFuLi4uQUJDREVGR0h", &mut bytes),
+        Err(InvalidBase64 {
+            index: 18,
+            byte: b'h'
+        })
+    );
+}
@@ -0,0 +1,60 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+macro_rules! bcd_matches_binary {
+    ($name:ident, $n:ident, $digits:literal) => {
+        #[test]
+        fn $name() {
+            let mut rng = SmallRng::from_seed([16; 16]);
+            let limit = (10 as $n).pow($digits);
+            for _ in 0..100_000 {
+                // Use fewer digits some of the time so that small numbers show up.
+                let digits = rng.gen_range(1, $digits + 1);
+                let a = rng.gen::<$n>() % (10 as $n).pow(digits);
+                let b = rng.gen::<$n>() % limit;
+                let (x, y) = (
+                    Bcd::<$n>::from_binary(a).unwrap(),
+                    Bcd::<$n>::from_binary(b).unwrap(),
+                );
+                assert_eq!(x.to_binary(), a);
+                assert_eq!(Bcd::<$n>::new(x.digits()), Some(x));
+                let ascii = format!("{:0width$}", a, width = $digits);
+                assert_eq!(&x.to_ascii()[..], ascii.as_bytes());
+                let mut array = x.to_ascii();
+                assert_eq!(Bcd::<$n>::from_ascii(array), Some(x));
+
+                let sum = (a + b) % limit;
+                assert_eq!(
+                    x.overflowing_add(y),
+                    (Bcd::<$n>::from_binary(sum).unwrap(), a + b >= limit)
+                );
+                let difference = (a + limit - b) % limit;
+                assert_eq!(
+                    x.overflowing_sub(y),
+                    (Bcd::<$n>::from_binary(difference).unwrap(), a < b)
+                );
+
+                // Anything other than a digit is rejected wherever it is.
+                let index = rng.gen_range(0, $digits);
+                array[index] = b"/:a \x00\xB0"[rng.gen_range(0, 6)];
+                assert_eq!(Bcd::<$n>::from_ascii(array), None);
+                let nibble = rng.gen_range(10, 16) as $n;
+                let digits = x.digits().0 & !((0xF as $n) << (4 * index)) | nibble << (4 * index);
+                assert_eq!(Bcd::<$n>::new(Bits4(digits)), None);
+            }
+            assert_eq!(Bcd::<$n>::from_binary(limit), None);
+            assert_eq!(Bcd::<$n>::MAX.to_binary(), limit - 1);
+        }
+    };
+}
+
+bcd_matches_binary!(bcd_matches_binary_u64, u64, 16);
+bcd_matches_binary!(bcd_matches_binary_u128, u128, 32);
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "attempt to subtract with overflow")]
+fn bcd_sub_panics_on_overflow() {
+    let _ = Bcd::<u64>::from_binary(1).unwrap() - Bcd::<u64>::from_binary(2).unwrap();
+}
@@ -0,0 +1,93 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use std::convert::TryInto;
+use swar::*;
+
+#[test]
+fn byte_search_matches_iterators() {
+    let mut rng = SmallRng::from_seed([7; 16]);
+    for len in 0..100 {
+        for _ in 0..100 {
+            // Use a small alphabet so that matches and misses both show up.
+            let haystack: Vec<u8> = (0..len).map(|_| rng.gen_range(0, 8)).collect();
+            let (a, b, c) = (
+                rng.gen_range(0, 10),
+                rng.gen_range(0, 10),
+                rng.gen_range(0, 10),
+            );
+            assert_eq!(
+                find_byte(&haystack, a),
+                haystack.iter().position(|&x| x == a)
+            );
+            assert_eq!(
+                find_any_of_3_bytes(&haystack, a, b, c),
+                haystack.iter().position(|&x| x == a || x == b || x == c)
+            );
+            assert_eq!(
+                count_byte(&haystack, a),
+                haystack.iter().filter(|&&x| x == a).count()
+            );
+        }
+    }
+}
+
+#[test]
+fn counts_match_iterators_past_the_counter_capacity() {
+    let mut rng = SmallRng::from_seed([19; 16]);
+    for &len in &[0, 1, 255 * 16, 255 * 16 + 1, 20_000] {
+        // Runs of one byte fill up its counters as fast as possible.
+        let mut bytes: Vec<u8> = (0..len).map(|_| rng.gen_range(0, 4)).collect();
+        bytes[len / 2..].iter_mut().for_each(|byte| *byte = 0xFF);
+        for needle in [0, 3, 0xFF].iter().copied() {
+            assert_eq!(
+                count_bytes(&bytes, needle),
+                bytes.iter().filter(|&&x| x == needle).count()
+            );
+        }
+        let mut expected = [0; 256];
+        for &byte in &bytes {
+            expected[byte as usize] += 1;
+        }
+        assert_eq!(byte_histogram(&bytes)[..], expected[..]);
+    }
+
+    let bytes: Vec<u8> = (0..10_000).map(|_| rng.gen()).collect();
+    let mut expected = [0; 256];
+    for &byte in &bytes {
+        expected[byte as usize] += 1;
+    }
+    assert_eq!(byte_histogram(&bytes)[..], expected[..]);
+}
+
+#[test]
+fn zero_lane_masks_match_bytes() {
+    let mut rng = SmallRng::from_seed([8; 16]);
+    for _ in 0..10_000 {
+        // Zero out random bytes and put ones right above some of them.
+        let mut bytes: [u8; 16] = rng.gen();
+        for byte in bytes.iter_mut() {
+            *byte = match rng.gen_range(0, 4) {
+                0 => 0,
+                1 => 1,
+                _ => *byte,
+            };
+        }
+        let expected = bytes
+            .iter()
+            .enumerate()
+            .filter(|&(_, &byte)| byte == 0)
+            .fold(0u128, |mask, (i, _)| mask | 0x80 << (8 * i));
+
+        let word = Bits8(u128::from_le_bytes(bytes));
+        assert_eq!(word.zero_lane_mask(), Bits8(expected));
+        assert_eq!(word.has_zero_lane(), expected != 0);
+
+        let low = Bits8(u64::from_le_bytes(bytes[..8].try_into().unwrap()));
+        assert_eq!(low.zero_lane_mask(), Bits8(expected as u64));
+        assert_eq!(low.has_zero_lane(), expected as u64 != 0);
+        assert_eq!(
+            low.zero_lane_mask().first_match_index(),
+            Bits8(expected as u64 as u128).first_match_index()
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use swar::*;
+
+/// Builds a number from the `width`-bit elements produced by `f`.
+fn lanes(width: u32, f: impl Fn(u32) -> u128) -> u128 {
+    (0..128 / width).fold(0, |n, i| n | f(i) << (i * width))
+}
+
+macro_rules! round_trips {
+    ($name:ident, $bits:ident, $width:expr) => {
+        #[test]
+        fn $name() {
+            let width: u32 = $width;
+            for offset in 0..=width {
+                // Rotate the values through the elements so that each one sees every value.
+                let value = |i: u32| ((i + offset) % (width + 1)) as u128;
+                let binary = $bits(lanes(width, value));
+                let thermometer = $bits(lanes(width, |i| (1 << value(i)) - 1));
+                assert_eq!(binary.binary_to_thermometer(), thermometer);
+                assert_eq!(thermometer.thermometer_to_binary(), binary);
+
+                let value = |i: u32| ((i + offset) % width) as u128;
+                let binary = $bits(lanes(width, value));
+                let one_hot = $bits(lanes(width, |i| 1 << value(i)));
+                assert_eq!(binary.binary_to_one_hot(), one_hot);
+                assert_eq!(one_hot.one_hot_to_binary(), binary);
+            }
+        }
+    };
+}
+
+round_trips!(bits2, Bits2, 2);
+round_trips!(bits4, Bits4, 4);
+round_trips!(bits8, Bits8, 8);
+round_trips!(bits16, Bits16, 16);
+round_trips!(bits32, Bits32, 32);
+round_trips!(bits64, Bits64, 64);
@@ -0,0 +1,68 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+#[test]
+fn parse_digits_matches_str_parse() {
+    let mut rng = SmallRng::from_seed([11; 16]);
+    for _ in 0..100_000 {
+        let mut bytes = [0u8; 16];
+        for byte in bytes.iter_mut() {
+            *byte = b'0' + rng.gen_range(0, 10);
+        }
+        // Swap in a byte that is near the digits some of the time.
+        if rng.gen() {
+            let byte = b"/:\0\x80\xB0 "[rng.gen_range(0, 6)];
+            bytes[rng.gen_range(0, 16)] = byte;
+        }
+        let parse = |digits: &[u8]| {
+            std::str::from_utf8(digits)
+                .ok()
+                .filter(|digits| digits.bytes().all(|byte| byte.is_ascii_digit()))
+                .map(|digits| digits.parse::<u64>().unwrap())
+        };
+        let mut low = [0; 8];
+        low.copy_from_slice(&bytes[..8]);
+        assert_eq!(
+            parse_digits8(u64::from_le_bytes(low)).map(u64::from),
+            parse(&low)
+        );
+        assert_eq!(parse_digits16(u128::from_le_bytes(bytes)), parse(&bytes));
+    }
+}
+
+#[test]
+fn parse_u64_matches_str_parse() {
+    let mut rng = SmallRng::from_seed([12; 16]);
+    let edges: [&[u8]; 8] = [
+        b"",
+        b"+",
+        b"0",
+        b"18446744073709551615",
+        b"18446744073709551616",
+        b"99999999999999999999",
+        b"0000000000000000000000000000018446744073709551615",
+        b"-1",
+    ];
+    for bytes in edges.iter() {
+        assert_eq!(
+            parse_u64(bytes),
+            std::str::from_utf8(bytes).unwrap().parse().ok()
+        );
+    }
+    for _ in 0..100_000 {
+        let len = rng.gen_range(0, 40);
+        let mut bytes: Vec<u8> = (0..len).map(|_| b'0' + rng.gen_range(0, 10)).collect();
+        // Lead with zeros, a sign or a bad byte some of the time.
+        match rng.gen_range(0, 8) {
+            0 => bytes = [vec![b'0'; rng.gen_range(0, 30)], bytes].concat(),
+            1 => bytes.insert(0, b'+'),
+            2 if len > 0 => bytes[rng.gen_range(0, len)] = b'x',
+            _ => (),
+        }
+        assert_eq!(
+            parse_u64(&bytes),
+            std::str::from_utf8(&bytes).unwrap().parse().ok()
+        );
+    }
+}
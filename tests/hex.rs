@@ -0,0 +1,62 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use std::fmt::Write;
+use swar::*;
+
+#[test]
+fn hex_matches_format() {
+    let mut rng = SmallRng::from_seed([13; 16]);
+    for _ in 0..100_000 {
+        let n: u64 = rng.gen();
+        let hex = format!("{:016x}", n);
+        assert_eq!(&hex_encode(n), hex.as_bytes());
+        assert_eq!(hex_decode(hex_encode(n)), Ok(n));
+        let mut upper = [0; 16];
+        upper.copy_from_slice(format!("{:016X}", n).as_bytes());
+        assert_eq!(hex_decode(upper), Ok(n));
+    }
+}
+
+#[test]
+fn hex_decode_rejects_every_other_byte() {
+    for byte in 0..=255u8 {
+        for index in 0..16 {
+            let mut hex = *b"0123456789abcdef";
+            hex[index] = byte;
+            if byte.is_ascii_hexdigit() {
+                assert!(hex_decode(hex).is_ok());
+            } else {
+                assert_eq!(hex_decode(hex), Err(InvalidHexDigit { index, byte }));
+            }
+        }
+    }
+}
+
+#[test]
+fn hex_slices_match_format() {
+    let mut rng = SmallRng::from_seed([14; 16]);
+    for len in 0..40 {
+        for _ in 0..100 {
+            let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            let mut expected = String::new();
+            for byte in &bytes {
+                write!(expected, "{:02x}", byte).unwrap();
+            }
+            let mut hex = vec![0; 2 * len];
+            hex_encode_slice(&bytes, &mut hex);
+            assert_eq!(hex, expected.as_bytes());
+
+            let mut decoded = vec![0; len];
+            assert_eq!(hex_decode_slice(&hex, &mut decoded), Ok(()));
+            assert_eq!(decoded, bytes);
+            if len > 0 {
+                let index = rng.gen_range(0, 2 * len);
+                hex[index] = b'g';
+                assert_eq!(
+                    hex_decode_slice(&hex, &mut decoded),
+                    Err(InvalidHexDigit { index, byte: b'g' })
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "index")]
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::index::HammingWeightTree;
+use swar::*;
+
+#[test]
+fn search_matches_brute_force() {
+    let mut rng = SmallRng::from_seed([5; 16]);
+    let centers = (0..16).map(|_| rng.gen::<u128>()).collect::<Vec<u128>>();
+    let codes = (0..5_000)
+        .map(|_| {
+            let center = centers[rng.gen_range(0, centers.len())];
+            center ^ (rng.gen::<u128>() & rng.gen::<u128>() & rng.gen::<u128>() & rng.gen::<u128>())
+        })
+        .collect::<Vec<u128>>();
+    let tree = codes.iter().cloned().collect::<HammingWeightTree>();
+    for &radius in &[0, 3, 8, 16, 24] {
+        for &center in &centers[..4] {
+            let mut expected = codes
+                .iter()
+                .enumerate()
+                .map(|(index, code)| Neighbor {
+                    distance: (code ^ center).count_ones(),
+                    index,
+                })
+                .filter(|neighbor| neighbor.distance <= radius)
+                .collect::<Vec<Neighbor>>();
+            expected.sort();
+            assert_eq!(tree.search(center, radius), expected);
+        }
+    }
+}
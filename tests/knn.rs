@@ -0,0 +1,44 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+#[test]
+fn knn_matches_sort() {
+    let mut rng = SmallRng::from_seed([5; 16]);
+    let descriptors = (0..10_000)
+        .map(|_| [rng.gen::<u128>(), rng.gen::<u128>()])
+        .collect::<Vec<[u128; 2]>>();
+    for _ in 0..20 {
+        let query = [rng.gen::<u128>(), rng.gen::<u128>()];
+        let mut expected = descriptors
+            .iter()
+            .enumerate()
+            .map(|(index, d)| Neighbor {
+                distance: (d[0] ^ query[0]).count_ones() + (d[1] ^ query[1]).count_ones(),
+                index,
+            })
+            .collect::<Vec<Neighbor>>();
+        expected.sort();
+        let mut buffer = [Neighbor::default(); 16];
+        assert_eq!(knn(&query, &descriptors, &mut buffer), &expected[..16]);
+    }
+}
+
+#[test]
+fn knn_fewer_descriptors_than_k() {
+    let descriptors = [[3u128], [1]];
+    let mut buffer = [Neighbor::default(); 4];
+    assert_eq!(
+        knn(&[0], &descriptors, &mut buffer),
+        [
+            Neighbor {
+                distance: 1,
+                index: 1
+            },
+            Neighbor {
+                distance: 2,
+                index: 0
+            }
+        ]
+    );
+}
@@ -0,0 +1,785 @@
+use swar::*;
+
+#[test]
+fn bits1_from_element() {
+    assert_eq!(
+        Bits1::from_element(1),
+        Bits1(0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF)
+    );
+    assert_eq!(
+        Bits1::from_element(0),
+        Bits1(0x0000_0000_0000_0000_0000_0000_0000_0000)
+    );
+}
+
+#[test]
+fn bits1_any() {
+    let input = Bits1(0b1010u128);
+    let output = Bits1(0b1010u128);
+    assert_eq!(input.any(), output);
+}
+
+#[test]
+fn bits1_union() {
+    let input = Bits1(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
+    let (left, right) = input.halve();
+    let output = Bits1::union(left, right);
+    assert_eq!(input, output);
+}
+
+#[test]
+fn bits1_pack_ones() {
+    let input = Bits1(0b00_01_10_11);
+    let out = Bits2(0b00_01_01_11);
+    assert_eq!(input.pack_ones(), out);
+}
+
+#[test]
+fn bits1_binary_to_thermometer() {
+    let binary = Bits1(0b1010);
+    let thermometer = Bits1(0b1010);
+    assert_eq!(binary.binary_to_thermometer(), thermometer);
+    assert_eq!(thermometer.thermometer_to_binary(), binary);
+}
+
+#[test]
+fn bits1_binary_to_one_hot() {
+    let binary = Bits1(0);
+    let one_hot = Bits1(0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF);
+    assert_eq!(binary.binary_to_one_hot(), one_hot);
+    assert_eq!(one_hot.one_hot_to_binary(), binary);
+}
+
+#[test]
+fn bits1_halve() {
+    let input = Bits1(0b1101 << 64 | 0b0101u128);
+    let (left, right) = input.halve();
+    assert_eq!(left, Bits2(0b0101_0001));
+    assert_eq!(right, Bits2(0b0001_0001));
+}
+
+#[test]
+fn bits2_from_element() {
+    assert_eq!(
+        Bits2::from_element(0b10),
+        Bits2(0xAAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA)
+    );
+}
+
+#[test]
+fn bits2_any() {
+    let input = Bits2(0b01101100u128);
+    let output = Bits2(0b01010100u128);
+    assert_eq!(input.any(), output);
+}
+
+#[test]
+fn bits2_union() {
+    let input = Bits2(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
+    let (left, right) = input.halve();
+    let output = Bits2::union(left, right);
+    assert_eq!(input, output);
+}
+
+#[test]
+fn bits2_pack_ones() {
+    let input = Bits2(0b11_01_01_00);
+    let out = Bits4(0b0111_0001);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08b} expected {:08b}",
+        input.pack_ones().0,
+        out.0
+    );
+
+    let input = Bits2(0b11_11_00_00);
+    let out = Bits4(0b1111_0000);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08b} expected {:08b}",
+        input.pack_ones().0,
+        out.0
+    );
+
+    let input = Bits2(0b01_01_00_01);
+    let out = Bits4(0b0011_0001);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08b} expected {:08b}",
+        input.pack_ones().0,
+        out.0
+    );
+
+    let input = Bits2(0b11_00_00_11);
+    let out = Bits4(0b0011_0011);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08b} expected {:08b}",
+        input.pack_ones().0,
+        out.0
+    );
+}
+
+#[test]
+fn bits2_pack_ones_swar() {
+    let input = Bits2(0x0137_8F6E);
+    let out = Bits4(0x0137_1F37);
+    assert_eq!(input.pack_ones_swar(), out);
+}
+
+#[test]
+fn bits2_binary_to_thermometer() {
+    let binary = Bits2(0b10_01_00);
+    let thermometer = Bits2(0b11_01_00);
+    assert_eq!(binary.binary_to_thermometer(), thermometer);
+    assert_eq!(thermometer.thermometer_to_binary(), binary);
+}
+
+#[test]
+fn bits2_binary_to_one_hot() {
+    let binary = Bits2(0x4444_4444_4444_4444_4444_4444_4444_4444);
+    let one_hot = Bits2(0x9999_9999_9999_9999_9999_9999_9999_9999);
+    assert_eq!(binary.binary_to_one_hot(), one_hot);
+    assert_eq!(one_hot.one_hot_to_binary(), binary);
+}
+
+#[test]
+fn bits2_minhwd() {
+    // All combinations of inputs 0-2 (hamming weights)
+    let a = Bits2(0b00_01_10_00_01_10_00_01_10u128);
+    let b = Bits2(0b00_00_00_01_01_01_10_10_10u128);
+    // Expected output weights
+    let e = Bits2(0b00_01_10_01_00_01_10_01_00u128);
+
+    assert_eq!(
+        a.minhwd(b),
+        e,
+        "got hamming distances {:b} expected {:b}",
+        a.minhwd(b).0,
+        e.0
+    );
+}
+
+#[test]
+fn bits2_maxhwd() {
+    // All combinations of inputs 0-2 (hamming weights)
+    let a = Bits2(0b00_01_10_00_01_10_00_01_10u128);
+    let b = Bits2(0b00_00_00_01_01_01_10_10_10u128);
+    // Expected output weights
+    let e = Bits2(0b00_01_10_01_10_01_10_01_00u128);
+
+    assert_eq!(
+        a.maxhwd(b),
+        e,
+        "got hamming distances {:b} expected {:b}",
+        a.maxhwd(b).0,
+        e.0
+    );
+}
+
+#[test]
+fn bits2_halve() {
+    let input = Bits2(0b1101 << 64 | 0b0101u128);
+    let (left, right) = input.halve();
+    assert_eq!(left, Bits4(0b0011_0001));
+    assert_eq!(right, Bits4(0b0001_0001));
+}
+
+#[test]
+fn bits4_from_element() {
+    assert_eq!(
+        Bits4::from_element(0b0110),
+        Bits4(0x6666_6666_6666_6666_6666_6666_6666_6666)
+    );
+}
+
+#[test]
+fn bits4_any() {
+    let input = Bits4(0xE_0u128);
+    let output = Bits4(0x1_0u128);
+    assert_eq!(input.any(), output);
+}
+
+#[test]
+fn bits4_union() {
+    let input = Bits4(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
+    let (left, right) = input.halve();
+    let output = Bits4::union(left, right);
+    assert_eq!(input, output);
+}
+
+#[test]
+fn bits4_pack_ones() {
+    let input = Bits4(0b1111_1111);
+    let out = Bits8(0b11111111);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08b} expected {:08b}",
+        input.pack_ones().0,
+        out.0
+    );
+
+    let input = Bits4(0b0111_0001);
+    let out = Bits8(0b00001111);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08b} expected {:08b}",
+        input.pack_ones().0,
+        out.0
+    );
+
+    let input = Bits4(0b1111_0000);
+    let out = Bits8(0b0000_1111);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08b} expected {:08b}",
+        input.pack_ones().0,
+        out.0
+    );
+
+    let input = Bits4(0b0011_0001);
+    let out = Bits8(0b0000_0111);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08b} expected {:08b}",
+        input.pack_ones().0,
+        out.0
+    );
+
+    let input = Bits4(0b0011_0011);
+    let out = Bits8(0b00001111);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08b} expected {:08b}",
+        input.pack_ones().0,
+        out.0
+    );
+}
+
+#[test]
+fn bits4_pack_ones_swar() {
+    let input = Bits4(0x0001_030F_FF80_77F0);
+    let out = Bits8(0x0001_030F_FF01_3F0F);
+    assert_eq!(input.pack_ones_swar(), out);
+}
+
+#[test]
+fn bits4_binary_to_thermometer() {
+    let binary = Bits4(0x4_3210);
+    let thermometer = Bits4(0xF_7310);
+    assert_eq!(binary.binary_to_thermometer(), thermometer);
+    assert_eq!(thermometer.thermometer_to_binary(), binary);
+}
+
+#[test]
+fn bits4_binary_to_one_hot() {
+    let binary = Bits4(0x3210_3210_3210_3210_3210_3210_3210_3210);
+    let one_hot = Bits4(0x8421_8421_8421_8421_8421_8421_8421_8421);
+    assert_eq!(binary.binary_to_one_hot(), one_hot);
+    assert_eq!(one_hot.one_hot_to_binary(), binary);
+}
+
+#[test]
+fn bits4_minhwd() {
+    for a in 0u128..=4 {
+        for b in 0u128..=4 {
+            let aa = Bits4(a | a << 4);
+            let bb = Bits4(b | b << 4);
+            let out = aa.minhwd(bb);
+            let diff = (a as i128 - b as i128).unsigned_abs();
+            let expected = Bits4(diff | diff << 4);
+            assert_eq!(
+                out, expected,
+                "got hamming distances {:08b} expected {:08b} ({:04b}, {:04b})",
+                out.0, expected.0, a, b
+            );
+        }
+    }
+}
+
+#[test]
+fn bits4_maxhwd() {
+    for a in 0u128..=4 {
+        for b in 0u128..=4 {
+            let aa = Bits4(a | a << 4);
+            let bb = Bits4(b | b << 4);
+            let out = aa.maxhwd(bb);
+            let max = core::cmp::min(a + b, 8 - a - b);
+            let expected = Bits4(max | max << 4);
+            assert_eq!(
+                out, expected,
+                "got hamming distances {:08b} expected {:08b} ({:04b}, {:04b})",
+                out.0, expected.0, a, b
+            );
+        }
+    }
+}
+
+#[test]
+fn bits4_halve() {
+    let input = Bits4(0xABCD << 64 | 0xDEAD);
+    let (left, right) = input.halve();
+    assert_eq!(left, Bits8(0x0A0B_0C0D));
+    assert_eq!(right, Bits8(0x0D0E_0A0D));
+}
+
+#[test]
+fn bits8_from_element() {
+    assert_eq!(
+        Bits8::from_element(0xFE),
+        Bits8(0xFEFE_FEFE_FEFE_FEFE_FEFE_FEFE_FEFE_FEFE)
+    );
+
+    // This can also be used to make constants.
+    const SPACES: Bits8<u128> = Bits8::from_element(0x20);
+    assert_eq!(SPACES, Bits8(0x2020_2020_2020_2020_2020_2020_2020_2020));
+}
+
+#[test]
+fn bits8_any() {
+    let input = Bits8(0xF0_00u128);
+    let output = Bits8(0x01_00u128);
+    assert_eq!(input.any(), output);
+}
+
+#[test]
+fn bits8_union() {
+    let input = Bits8(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
+    let (left, right) = input.halve();
+    let output = Bits8::union(left, right);
+    assert_eq!(input, output);
+}
+
+#[test]
+fn bits8_pack_ones() {
+    let input = Bits8(0x0101_FFFF);
+    let out = Bits16(0x0003_FFFF);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08X} expected {:08X}",
+        input.pack_ones().0,
+        out.0
+    );
+
+    let input = Bits8(0x0000_FFFF_0F0F_00FF);
+    let out = Bits16(0x0000_FFFF_00FF_00FF);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:016X} expected {:016X}",
+        input.pack_ones().0,
+        out.0
+    );
+}
+
+#[test]
+fn bits8_pack_ones_swar() {
+    let input = Bits8(0x0000_0101_FFFF_8421_00FF);
+    let out = Bits16(0x0000_0003_FFFF_000F_00FF);
+    assert_eq!(input.pack_ones_swar(), out);
+}
+
+#[test]
+fn bits8_binary_to_thermometer() {
+    let binary = Bits8(0x08_05_01_00);
+    let thermometer = Bits8(0xFF_1F_01_00);
+    assert_eq!(binary.binary_to_thermometer(), thermometer);
+    assert_eq!(thermometer.thermometer_to_binary(), binary);
+}
+
+#[test]
+fn bits8_binary_to_one_hot() {
+    let binary = Bits8(0x0706_0504_0302_0100_0706_0504_0302_0100);
+    let one_hot = Bits8(0x8040_2010_0804_0201_8040_2010_0804_0201);
+    assert_eq!(binary.binary_to_one_hot(), one_hot);
+    assert_eq!(one_hot.one_hot_to_binary(), binary);
+}
+
+#[test]
+fn bits8_minhwd() {
+    let bits = 8;
+    for a in 0u128..=bits as u128 {
+        for b in 0u128..=bits as u128 {
+            let aa = Bits8(a | a << bits);
+            let bb = Bits8(b | b << bits);
+            let out = aa.minhwd(bb);
+            let diff = (a as i128 - b as i128).unsigned_abs();
+            let expected = Bits8(diff | diff << bits);
+            assert_eq!(
+                out, expected,
+                "got hamming distances {:016b} expected {:016b} ({:08b}, {:08b})",
+                out.0, expected.0, a, b
+            );
+        }
+    }
+}
+
+#[test]
+fn bits8_maxhwd() {
+    let bits = 8;
+    for a in 0u128..=bits as u128 {
+        for b in 0u128..=bits as u128 {
+            let aa = Bits8(a | a << bits);
+            let bb = Bits8(b | b << bits);
+            let out = aa.maxhwd(bb);
+            let max = core::cmp::min(a + b, 2 * bits as u128 - a - b);
+            let expected = Bits8(max | max << bits);
+            assert_eq!(out, expected);
+        }
+    }
+}
+
+#[test]
+fn bits8_halve() {
+    let input = Bits8(0xABCD << 64 | 0xDEAD);
+    let (left, right) = input.halve();
+    assert_eq!(left, Bits16(0x00AB_00CD));
+    assert_eq!(right, Bits16(0x00DE_00AD));
+}
+
+#[test]
+fn bits16_from_element() {
+    assert_eq!(
+        Bits16::from_element(0xFEED),
+        Bits16(0xFEED_FEED_FEED_FEED_FEED_FEED_FEED_FEED)
+    );
+}
+
+#[test]
+fn bits16_any() {
+    let input = Bits16(0x8000_0000u128);
+    let output = Bits16(0x0001_0000u128);
+    assert_eq!(input.any(), output);
+}
+
+#[test]
+fn bits16_union() {
+    let input = Bits16(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
+    let (left, right) = input.halve();
+    let output = Bits16::union(left, right);
+    assert_eq!(input, output);
+}
+
+#[test]
+fn bits16_pack_ones() {
+    let input = Bits16(0xFFFF_0000);
+    let out = Bits32(0x0000_FFFF);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08X} expected {:08X}",
+        input.pack_ones().0,
+        out.0
+    );
+
+    let input = Bits16(0x00FF_007F);
+    let out = Bits32(0x0000_7FFF);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08X} expected {:08X}",
+        input.pack_ones().0,
+        out.0
+    );
+}
+
+#[test]
+fn bits16_pack_ones_swar() {
+    let input = Bits16(0x0000_0000_0001_0001_FFFF_FFFF_00FF_007F);
+    let out = Bits32(0x0000_0000_0000_0003_FFFF_FFFF_0000_7FFF);
+    assert_eq!(input.pack_ones_swar(), out);
+}
+
+#[test]
+fn bits16_binary_to_thermometer() {
+    let binary = Bits16(0x0010_0009_0000);
+    let thermometer = Bits16(0xFFFF_01FF_0000);
+    assert_eq!(binary.binary_to_thermometer(), thermometer);
+    assert_eq!(thermometer.thermometer_to_binary(), binary);
+}
+
+#[test]
+fn bits16_binary_to_one_hot() {
+    let binary = Bits16(0x000F_0009_0004_0000_000F_0009_0004_0000);
+    let one_hot = Bits16(0x8000_0200_0010_0001_8000_0200_0010_0001);
+    assert_eq!(binary.binary_to_one_hot(), one_hot);
+    assert_eq!(one_hot.one_hot_to_binary(), binary);
+}
+
+#[test]
+fn bits16_minhwd() {
+    let bits = 16;
+    for a in 0u128..=bits as u128 {
+        for b in 0u128..=bits as u128 {
+            let aa = Bits16(a | a << bits);
+            let bb = Bits16(b | b << bits);
+            let out = aa.minhwd(bb);
+            let diff = (a as i128 - b as i128).unsigned_abs();
+            let expected = Bits16(diff | diff << bits);
+            assert_eq!(out, expected);
+        }
+    }
+}
+
+#[test]
+fn bits16_maxhwd() {
+    let bits = 16;
+    for a in 0u128..=bits as u128 {
+        for b in 0u128..=bits as u128 {
+            let aa = Bits16(a | a << bits);
+            let bb = Bits16(b | b << bits);
+            let out = aa.maxhwd(bb);
+            let max = core::cmp::min(a + b, 2 * bits as u128 - a - b);
+            let expected = Bits16(max | max << bits);
+            assert_eq!(out, expected);
+        }
+    }
+}
+
+#[test]
+fn bits16_halve() {
+    let input = Bits16(0x0BAD_CAFE << 64 | 0xDEAD_BEEF);
+    let (left, right) = input.halve();
+    assert_eq!(left, Bits32(0x0000_0BAD_0000_CAFE));
+    assert_eq!(right, Bits32(0x0000_DEAD_0000_BEEF));
+}
+
+#[test]
+fn bits32_from_element() {
+    assert_eq!(
+        Bits32::from_element(0xFEED_FACE),
+        Bits32(0xFEED_FACE_FEED_FACE_FEED_FACE_FEED_FACE)
+    );
+}
+
+#[test]
+fn bits32_any() {
+    let input = Bits32(0x8000_0000_0000_0000u128);
+    let output = Bits32(0x0000_0001_0000_0000u128);
+    assert_eq!(input.any(), output);
+}
+
+#[test]
+fn bits32_union() {
+    let input = Bits32(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
+    let (left, right) = input.halve();
+    let output = Bits32::union(left, right);
+    assert_eq!(input, output);
+}
+
+#[test]
+fn bits32_pack_ones() {
+    let input = Bits32(0x0000_FFFF);
+    let out = Bits64(0x0000_FFFF);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08X} expected {:08X}",
+        input.pack_ones().0,
+        out.0
+    );
+
+    let input = Bits32(0x00FF_007F);
+    let out = Bits64(0x0000_7FFF);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08X} expected {:08X}",
+        input.pack_ones().0,
+        out.0
+    );
+}
+
+#[test]
+fn bits32_pack_ones_swar() {
+    let input = Bits32(0xFFFF_FFFF_FFFF_FFFF_0000_0001_0000_0003);
+    let out = Bits64(0xFFFF_FFFF_FFFF_FFFF_0000_0000_0000_0007);
+    assert_eq!(input.pack_ones_swar(), out);
+}
+
+#[test]
+fn bits32_binary_to_thermometer() {
+    let binary = Bits32(0x0000_0020_0000_0011);
+    let thermometer = Bits32(0xFFFF_FFFF_0001_FFFF);
+    assert_eq!(binary.binary_to_thermometer(), thermometer);
+    assert_eq!(thermometer.thermometer_to_binary(), binary);
+}
+
+#[test]
+fn bits32_binary_to_one_hot() {
+    let binary = Bits32(0x0000_001F_0000_0000_0000_001F_0000_0000);
+    let one_hot = Bits32(0x8000_0000_0000_0001_8000_0000_0000_0001);
+    assert_eq!(binary.binary_to_one_hot(), one_hot);
+    assert_eq!(one_hot.one_hot_to_binary(), binary);
+}
+
+#[test]
+fn bits32_minhwd() {
+    let bits = 32;
+    for a in 0u128..=bits as u128 {
+        for b in 0u128..=bits as u128 {
+            let aa = Bits32(a | a << bits);
+            let bb = Bits32(b | b << bits);
+            let out = aa.minhwd(bb);
+            let diff = (a as i128 - b as i128).unsigned_abs();
+            let expected = Bits32(diff | diff << bits);
+            assert_eq!(out, expected);
+        }
+    }
+}
+
+#[test]
+fn bits32_maxhwd() {
+    let bits = 32;
+    for a in 0u128..=bits as u128 {
+        for b in 0u128..=bits as u128 {
+            let aa = Bits32(a | a << bits);
+            let bb = Bits32(b | b << bits);
+            let out = aa.maxhwd(bb);
+            let max = core::cmp::min(a + b, 2 * bits as u128 - a - b);
+            let expected = Bits32(max | max << bits);
+            assert_eq!(out, expected);
+        }
+    }
+}
+
+#[test]
+fn bits32_halve() {
+    let input = Bits32(0x0BAD_CAFE << 64 | 0xDEAD_BEEF);
+    let (left, right) = input.halve();
+    assert_eq!(left, Bits64(0x0BAD_CAFE));
+    assert_eq!(right, Bits64(0xDEAD_BEEF));
+}
+
+#[test]
+fn bits64_from_element() {
+    assert_eq!(
+        Bits64::from_element(0xFEED_FACE_CAFE_BEEF),
+        Bits64(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF)
+    );
+}
+
+#[test]
+fn bits64_any() {
+    let input = Bits64(0x8000_0000_0000_0000_0000_0000_0000_0000u128);
+    let output = Bits64(0x0000_0000_0000_0001_0000_0000_0000_0000u128);
+    assert_eq!(input.any(), output);
+}
+
+#[test]
+fn bits64_union() {
+    let input = Bits64(0xFEED_FACE_CAFE_BEEF_FEED_FACE_CAFE_BEEF);
+    let (left, right) = input.halve();
+    let output = Bits64::union(left, right);
+    assert_eq!(input, output);
+}
+
+#[test]
+fn bits64_pack_ones() {
+    let input = Bits64(0x00FF_00FF);
+    let out = Bits128(0x0000_FFFF);
+    assert_eq!(
+        input.pack_ones(),
+        out,
+        "got {:08X} expected {:08X}",
+        input.pack_ones().0,
+        out.0
+    );
+}
+
+#[test]
+fn bits64_binary_to_thermometer() {
+    let binary = Bits64(64 << 64 | 3);
+    let thermometer = Bits64(0xFFFF_FFFF_FFFF_FFFF_0000_0000_0000_0007);
+    assert_eq!(binary.binary_to_thermometer(), thermometer);
+    assert_eq!(thermometer.thermometer_to_binary(), binary);
+}
+
+#[test]
+fn bits64_binary_to_one_hot() {
+    let binary = Bits64(63 << 64);
+    let one_hot = Bits64(1 << 127 | 1);
+    assert_eq!(binary.binary_to_one_hot(), one_hot);
+    assert_eq!(one_hot.one_hot_to_binary(), binary);
+}
+
+#[test]
+fn bits64_minhwd() {
+    let bits = 64;
+    for a in 0u128..=bits as u128 {
+        for b in 0u128..=bits as u128 {
+            let aa = Bits64(a | a << bits);
+            let bb = Bits64(b | b << bits);
+            let out = aa.minhwd(bb);
+            let diff = (a as i128 - b as i128).unsigned_abs();
+            let expected = Bits64(diff | diff << bits);
+            assert_eq!(out, expected);
+        }
+    }
+}
+
+#[test]
+fn bits64_maxhwd() {
+    let bits = 64;
+    for a in 0u128..=bits as u128 {
+        for b in 0u128..=bits as u128 {
+            let aa = Bits64(a | a << bits);
+            let bb = Bits64(b | b << bits);
+            let out = aa.maxhwd(bb);
+            let max = core::cmp::min(a + b, 2 * bits as u128 - a - b);
+            let expected = Bits64(max | max << bits);
+            assert_eq!(out, expected);
+        }
+    }
+}
+
+#[test]
+fn bits64_halve() {
+    let input = Bits64(0x0BAD_CAFE << 64 | 0xDEAD_BEEF);
+    let (left, right) = input.halve();
+    assert_eq!(left, Bits128(0x0BAD_CAFE));
+    assert_eq!(right, Bits128(0xDEAD_BEEF));
+}
+
+#[test]
+fn bits128_any() {
+    let input = Bits128(0x8000_0000_0000_0000_0000_0000_0000_0000u128);
+    let output = Bits128(0x0000_0000_0000_0000_0000_0000_0000_0001u128);
+    assert_eq!(input.any(), output);
+
+    let input = Bits128(0x0000_0000_0000_0000_0000_0000_0000_0000u128);
+    let output = Bits128(0x0000_0000_0000_0000_0000_0000_0000_0000u128);
+    assert_eq!(input.any(), output);
+}
+
+#[test]
+fn bits128_binary_to_thermometer() {
+    let binary = Bits128(5);
+    let thermometer = Bits128(0x1F);
+    assert_eq!(binary.binary_to_thermometer(), thermometer);
+    assert_eq!(thermometer.thermometer_to_binary(), binary);
+}
+
+#[test]
+fn bits128_binary_to_one_hot() {
+    let binary = Bits128(127);
+    let one_hot = Bits128(1 << 127);
+    assert_eq!(binary.binary_to_one_hot(), one_hot);
+    assert_eq!(one_hot.one_hot_to_binary(), binary);
+}
+
+#[test]
+fn bits128_maxhwd() {
+    assert_eq!(Bits128(100).maxhwd(Bits128(100)), Bits128(56));
+    assert_eq!(Bits128(3).maxhwd(Bits128(5)), Bits128(8));
+}
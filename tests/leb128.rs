@@ -0,0 +1,118 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+/// Encodes `n` 7 bits at a time, padded out to at least `len` bytes.
+fn encode(mut n: u64, len: usize, bytes: &mut Vec<u8>) {
+    let mut written = 0;
+    loop {
+        let byte = (n & 0x7F) as u8;
+        n >>= 7;
+        written += 1;
+        if n == 0 && written >= len {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Decodes the integer at the start of `bytes` one byte at a time.
+fn decode(bytes: &[u8]) -> Result<(u64, usize), InvalidLeb128> {
+    let mut n = 0u128;
+    for (i, &byte) in bytes.iter().enumerate() {
+        if i == 10 {
+            break;
+        }
+        n |= ((byte & 0x7F) as u128) << (7 * i);
+        if byte & 0x80 == 0 {
+            return if n > u64::MAX as u128 {
+                Err(InvalidLeb128 {
+                    index: 0,
+                    truncated: false,
+                })
+            } else {
+                Ok((n as u64, i + 1))
+            };
+        }
+    }
+    Err(InvalidLeb128 {
+        index: 0,
+        truncated: bytes.len() < 10,
+    })
+}
+
+#[test]
+fn leb128_matches_scalar() {
+    let mut rng = SmallRng::from_seed([17; 16]);
+    for _ in 0..100_000 {
+        // Spread the values out over every length.
+        let n = rng.gen::<u64>() >> rng.gen_range(0, 64);
+        let mut bytes = Vec::new();
+        encode(n, rng.gen_range(0, 12), &mut bytes);
+        bytes.extend((0..rng.gen_range(0, 4)).map(|_| rng.gen::<u8>()));
+        // Cut the bytes short or flip a bit some of the time.
+        match rng.gen_range(0, 4) {
+            0 => bytes.truncate(rng.gen_range(0, bytes.len() + 1)),
+            1 => {
+                let index = rng.gen_range(0, bytes.len());
+                bytes[index] ^= 1 << rng.gen_range(0, 8);
+            }
+            _ => (),
+        }
+        assert_eq!(decode_leb128(&bytes), decode(&bytes), "{:X?}", bytes);
+    }
+}
+
+#[test]
+fn leb128_slice_matches_scalar() {
+    let mut rng = SmallRng::from_seed([18; 16]);
+    for _ in 0..10_000 {
+        let expected: Vec<u64> = (0..rng.gen_range(0, 20))
+            .map(|_| rng.gen::<u64>() >> rng.gen_range(0, 64))
+            .collect();
+        let mut bytes = Vec::new();
+        for &n in &expected {
+            encode(n, rng.gen_range(0, 3), &mut bytes);
+        }
+        let mut values = vec![0; expected.len()];
+        assert_eq!(
+            decode_leb128_slice(&bytes, &mut values),
+            Ok((expected.len(), bytes.len()))
+        );
+        assert_eq!(values, expected);
+
+        // Stopping early only reads the integers that were written.
+        let count = rng.gen_range(0, expected.len() + 1);
+        let mut read = 0;
+        for _ in 0..count {
+            read += decode_leb128(&bytes[read..]).unwrap().1;
+        }
+        assert_eq!(
+            decode_leb128_slice(&bytes, &mut values[..count]),
+            Ok((count, read))
+        );
+
+        // A bad integer is reported where it starts.
+        if count < expected.len() {
+            let mut bad = bytes[..read].to_vec();
+            bad.extend_from_slice(&[0xFF; 10]);
+            bad.push(0x01);
+            assert_eq!(
+                decode_leb128_slice(&bad, &mut values),
+                Err(InvalidLeb128 {
+                    index: read,
+                    truncated: false,
+                })
+            );
+            bad.truncate(read + 5);
+            assert_eq!(
+                decode_leb128_slice(&bad, &mut values),
+                Err(InvalidLeb128 {
+                    index: read,
+                    truncated: true,
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "alloc")]
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+#[test]
+fn search_matches_brute_force() {
+    let mut rng = SmallRng::from_seed([5; 16]);
+    // Make clusters of codes so that every radius finds something.
+    let centers = (0..32)
+        .map(|_| [rng.gen::<u128>(), rng.gen::<u128>()])
+        .collect::<Vec<[u128; 2]>>();
+    let codes = (0..20_000)
+        .map(|_| {
+            let center = centers[rng.gen_range(0, centers.len())];
+            let mut code = center;
+            for _ in 0..rng.gen_range(0, 40) {
+                let bit = rng.gen_range(0, 256);
+                code[bit / 128] ^= 1 << (bit % 128);
+            }
+            code
+        })
+        .collect::<Vec<[u128; 2]>>();
+    let index = MultiIndex::new(codes.clone());
+    for &radius in &[0, 5, 15, 16, 31, 40] {
+        for center in &centers[..4] {
+            let expected = codes
+                .iter()
+                .enumerate()
+                .map(|(index, code)| Neighbor {
+                    distance: (code[0] ^ center[0]).count_ones()
+                        + (code[1] ^ center[1]).count_ones(),
+                    index,
+                })
+                .filter(|neighbor| neighbor.distance <= radius)
+                .collect::<Vec<Neighbor>>();
+            let mut expected = expected;
+            expected.sort();
+            assert_eq!(index.search(center, radius), expected);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+/// Applies `f` to every pair of `width`-bit elements one element at a time.
+fn zip_lanes(a: u128, b: u128, width: u32, f: impl Fn(u128, u128) -> u128) -> u128 {
+    let lane = u128::MAX >> (128 - width);
+    (0..128 / width).fold(0, |out, i| {
+        let shift = i * width;
+        out | (f(a >> shift & lane, b >> shift & lane) & lane) << shift
+    })
+}
+
+macro_rules! lanes_match_scalar {
+    ($name:ident, $width:literal) => {
+        #[test]
+        fn $name() {
+            let width: u32 = $width;
+            let mut rng = SmallRng::from_seed([$width; 16]);
+            for _ in 0..10_000 {
+                // Share some elements between the inputs so that equal elements show up.
+                let a = rng.gen::<u128>();
+                let b = a ^ rng.gen::<u128>() & rng.gen::<u128>() & rng.gen::<u128>();
+                let (x, y) = (Bits::<$width, u128>(a), Bits::<$width, u128>(b));
+                let lane = u128::MAX >> (128 - width);
+
+                assert_eq!(
+                    x.wrapping_add(y).0,
+                    zip_lanes(a, b, width, |a, b| a.wrapping_add(b))
+                );
+                let overflows = zip_lanes(a, b, width, |a, b| (a > lane - b) as u128);
+                assert_eq!(x.overflowing_add(y), (x.wrapping_add(y), Bits(overflows)));
+                assert_eq!(x.checked_add(y).is_some(), overflows == 0);
+                assert_eq!(
+                    x.wrapping_sub(y).0,
+                    zip_lanes(a, b, width, |a, b| a.wrapping_sub(b))
+                );
+                assert_eq!(
+                    x.lanes_eq(y).0,
+                    zip_lanes(a, b, width, |a, b| (a == b) as u128)
+                );
+                assert_eq!(
+                    x.lanes_lt(y).0,
+                    zip_lanes(a, b, width, |a, b| (a < b) as u128)
+                );
+                assert_eq!(
+                    x.lanes_gt(y).0,
+                    zip_lanes(a, b, width, |a, b| (a > b) as u128)
+                );
+                assert_eq!(x.any().0, zip_lanes(a, b, width, |a, _| (a != 0) as u128));
+                assert_eq!(
+                    x.abs_diff(y).0,
+                    zip_lanes(a, b, width, |a, b| a.max(b) - a.min(b))
+                );
+
+                let index = rng.gen_range(0, Bits::<$width, u128>::LANES);
+                assert_eq!(x.extract(index), a >> (index * width) & lane);
+                let inserted = x.insert(index, b & lane);
+                assert_eq!(inserted.extract(index), b & lane);
+                assert_eq!(
+                    inserted.0 & !(lane << (index * width)),
+                    a & !(lane << (index * width))
+                );
+
+                let element = b & lane;
+                assert_eq!(
+                    Bits::<$width, u128>::from_element(element).0,
+                    zip_lanes(a, b, width, |_, _| element)
+                );
+                assert_eq!(
+                    Bits::<$width, u128>::try_from_element(element),
+                    Ok(Bits::<$width, u128>::from_element(element))
+                );
+                if lane != u128::MAX {
+                    let value = a | !lane;
+                    assert_eq!(
+                        Bits::<$width, u128>::try_from_element(value),
+                        Err(ElementOutOfRange { width, value })
+                    );
+                }
+            }
+        }
+    };
+}
+
+lanes_match_scalar!(lanes_match_scalar_3, 3);
+lanes_match_scalar!(lanes_match_scalar_5, 5);
+lanes_match_scalar!(lanes_match_scalar_6, 6);
+lanes_match_scalar!(lanes_match_scalar_7, 7);
+lanes_match_scalar!(lanes_match_scalar_8, 8);
+lanes_match_scalar!(lanes_match_scalar_10, 10);
+lanes_match_scalar!(lanes_match_scalar_12, 12);
+lanes_match_scalar!(lanes_match_scalar_21, 21);
+lanes_match_scalar!(lanes_match_scalar_128, 128);
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "overflow in an element")]
+fn add_panics_on_element_overflow() {
+    let _ = Bits8(0x00FF) + Bits8(0x0001);
+}
@@ -0,0 +1,59 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+#[test]
+fn operators_match_u128() {
+    let mut rng = SmallRng::from_seed([6; 16]);
+    for _ in 0..10_000 {
+        let (a, b) = (rng.gen::<u128>(), rng.gen::<u128>());
+        let shift = rng.gen_range(0, 128);
+        let (x, y) = (Bits8(a), Bits8(b));
+        assert_eq!(x & y, Bits8(a & b));
+        assert_eq!(x & b, Bits8(a & b));
+        assert_eq!(x | y, Bits8(a | b));
+        assert_eq!(x ^ y, Bits8(a ^ b));
+        assert_eq!(!x, Bits8(!a));
+        assert_eq!(x << shift, Bits8(a << shift));
+        assert_eq!(x >> shift, Bits8(a >> shift));
+        assert_eq!(u128::from(x), a);
+        assert_eq!(Bits8::from(a), x);
+
+        let mut z = x;
+        z &= y;
+        z |= Bits8(a >> 1);
+        z ^= y;
+        z &= b >> 3;
+        z <<= 2;
+        z >>= 1;
+        assert_eq!(z, Bits8((((a & b | a >> 1) ^ b) & b >> 3) << 2 >> 1));
+    }
+}
+
+#[test]
+fn arithmetic_operators_keep_elements_apart() {
+    // The high bit of each element is cleared so that nothing overflows.
+    let a = Bits4(0x1234_5670_7777_0000);
+    let b = Bits4(0x1111_1110_0123_0000);
+    let mut c = a;
+    c += b;
+    assert_eq!(c, Bits4(0x2345_6780_789A_0000));
+    c -= b;
+    assert_eq!(c, a);
+    assert_eq!(a + b - a, b);
+}
+
+#[test]
+fn unused_bits_stay_clear() {
+    // A 10-bit element leaves the top 8 bits unused.
+    let unused = !(u128::MAX >> 8);
+    assert_eq!((!Bits10(0)).0 & unused, 0);
+    assert_eq!((Bits10(u128::MAX >> 8) << 4).0 & unused, 0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "overflow in an element")]
+fn sub_panics_on_element_underflow() {
+    let _ = Bits8(0x0100) - Bits8(0x0001);
+}
@@ -0,0 +1,58 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+/// Sqishes the bits to the right in each `width`-bit segment one segment at a time.
+fn pack_lanes(x: u128, width: u32) -> u128 {
+    let lane = u128::MAX >> (128 - width);
+    (0..128).step_by(width as usize).fold(0, |out, shift| {
+        let count = (x >> shift & lane).count_ones();
+        // An empty 128-bit segment would shift by the whole width.
+        out | lane.checked_shr(width - count).unwrap_or(0) << shift
+    })
+}
+
+#[test]
+fn pack_ones_exhaustive_small_widths() {
+    // Every 4-bit and 8-bit segment is checked, with different values in each segment.
+    for n in 0..=255u128 {
+        let x = Bits8::from_element(n).0 ^ 0x0011_2233_4455_6677_8899_AABB_CCDD_EEFF;
+        assert_eq!(Bits2(x).pack_ones_swar().0, pack_lanes(x, 4));
+        assert_eq!(Bits4(x).pack_ones_swar().0, pack_lanes(x, 8));
+        assert_eq!(Bits2(x).pack_ones(), Bits2(x).pack_ones_swar());
+        assert_eq!(Bits4(x).pack_ones(), Bits4(x).pack_ones_swar());
+    }
+    for n in 0..=0xFFFFu128 {
+        let x = Bits16::from_element(n).0 ^ 0x0000_0001_0102_0304_8421_1248_F0F0_FFFF;
+        assert_eq!(Bits8(x).pack_ones_swar().0, pack_lanes(x, 16));
+        assert_eq!(Bits8(x).pack_ones(), Bits8(x).pack_ones_swar());
+    }
+}
+
+#[test]
+fn pack_ones_random_large_widths() {
+    let mut rng = SmallRng::from_seed([5; 16]);
+    for _ in 0..100_000 {
+        // Mask out random bits so that every count is likely to show up.
+        let x = rng.gen::<u128>() & rng.gen::<u128>() | rng.gen::<u128>() & rng.gen::<u128>();
+        assert_eq!(Bits16(x).pack_ones_swar().0, pack_lanes(x, 32));
+        assert_eq!(Bits32(x).pack_ones_swar().0, pack_lanes(x, 64));
+        assert_eq!(Bits16(x).pack_ones(), Bits16(x).pack_ones_swar());
+        assert_eq!(Bits32(x).pack_ones(), Bits32(x).pack_ones_swar());
+        assert_eq!(Bits64(x).pack_ones_swar().0, pack_lanes(x, 128));
+        assert_eq!(Bits64(x).pack_ones(), Bits64(x).pack_ones_swar());
+    }
+    for &x in &[
+        0,
+        u128::MAX,
+        u64::MAX as u128,
+        (u64::MAX as u128) << 64,
+        0xFFFF_FFFF_0000_0000_FFFF_FFFF,
+        1 << 127,
+    ] {
+        assert_eq!(Bits16(x).pack_ones_swar().0, pack_lanes(x, 32));
+        assert_eq!(Bits32(x).pack_ones_swar().0, pack_lanes(x, 64));
+        assert_eq!(Bits64(x).pack_ones_swar().0, pack_lanes(x, 128));
+        assert_eq!(Bits64(x).pack_ones(), Bits64(x).pack_ones_swar());
+    }
+}
@@ -0,0 +1,30 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+#[test]
+fn try_new_rejects_bits_outside_the_elements() {
+    let mut rng = SmallRng::from_seed([22; 16]);
+    for _ in 0..10_000 {
+        // 6-bit elements with 4 guard bits leave 8 bits above the last lane.
+        let elements = (0..12).fold(0u128, |n, lane| n | 0x3F << (10 * lane));
+        let n = rng.gen::<u128>() & elements;
+        assert_eq!(Padded::<Bits6, 4>::try_new(n), Ok(Padded::new(n)));
+
+        let bit = rng.gen_range(0, 128);
+        if elements >> bit & 1 == 0 {
+            let value = (n | 1 << bit) >> (bit / 10 * 10) & 0x3FF;
+            assert_eq!(
+                Padded::<Bits6, 4>::try_new(n | 1 << bit),
+                Err(ElementOutOfRange { width: 6, value })
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "element does not fit")]
+fn new_panics_on_set_guard_bits() {
+    let _ = Padded::<Bits7, 1>::new(0xFF_80_7F);
+}
@@ -0,0 +1,42 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+#[test]
+fn hamming_distance_bounds() {
+    let mut rng = SmallRng::from_seed([5; 16]);
+    for _ in 0..100_000 {
+        let a: u128 = rng.gen();
+        // Flip a random number of bits so that close distances are tested too.
+        let b = a ^ (rng.gen::<u128>() & rng.gen::<u128>() & rng.gen::<u128>());
+        let distance = (a ^ b).count_ones();
+        let a = WeightPyramid::new(a);
+        let b = WeightPyramid::new(b);
+        let lower = a.minhwd(&b).sum_weights();
+        let upper = a.maxhwd(&b).sum_weights();
+        assert_eq!(lower[0], distance);
+        assert_eq!(upper[0], distance);
+        for level in 1..8 {
+            assert!(lower[level] <= lower[level - 1]);
+            assert!(upper[level] >= upper[level - 1]);
+        }
+    }
+}
+
+#[test]
+fn within_distance_exact() {
+    let mut rng = SmallRng::from_seed([7; 16]);
+    for _ in 0..10_000 {
+        let a: [u128; 4] = rng.gen();
+        let mut b = a;
+        for word in &mut b {
+            *word ^= rng.gen::<u128>() & rng.gen::<u128>() & rng.gen::<u128>() & rng.gen::<u128>();
+        }
+        let distance: u32 = a.iter().zip(&b).map(|(a, b)| (a ^ b).count_ones()).sum();
+        let a: Vec<WeightPyramid> = a.iter().map(|&n| WeightPyramid::new(n)).collect();
+        let b: Vec<WeightPyramid> = b.iter().map(|&n| WeightPyramid::new(n)).collect();
+        let k = rng.gen_range(0, 64);
+        let expected = if distance <= k { Some(distance) } else { None };
+        assert_eq!(within_distance(&a, &b, k), expected);
+    }
+}
@@ -0,0 +1,61 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+#[test]
+fn sad_matches_scalar() {
+    let mut rng = SmallRng::from_seed([20; 16]);
+    for _ in 0..10_000 {
+        let (a, b) = (rng.gen::<[u8; 16]>(), rng.gen::<[u8; 16]>());
+        let expected: u32 = a
+            .iter()
+            .zip(&b)
+            .map(|(&a, &b)| (a as i32 - b as i32).unsigned_abs())
+            .sum();
+        assert_eq!(
+            Bits8(u128::from_le_bytes(a)).sad(Bits8(u128::from_le_bytes(b))),
+            expected
+        );
+        // The same bytes read as 16-bit elements.
+        let expected: u32 = a
+            .chunks(2)
+            .zip(b.chunks(2))
+            .map(|(a, b)| {
+                let a = u16::from_le_bytes([a[0], a[1]]) as i32;
+                let b = u16::from_le_bytes([b[0], b[1]]) as i32;
+                (a - b).unsigned_abs()
+            })
+            .sum();
+        assert_eq!(
+            Bits16(u128::from_le_bytes(a)).sad(Bits16(u128::from_le_bytes(b))),
+            expected
+        );
+    }
+}
+
+#[test]
+fn slice_sad_matches_scalar() {
+    let mut rng = SmallRng::from_seed([21; 16]);
+    for len in 0..100 {
+        for _ in 0..100 {
+            let a: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            let b: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            assert_eq!(
+                sad(&a, &b),
+                a.iter()
+                    .zip(&b)
+                    .map(|(&a, &b)| (a as i64 - b as i64).unsigned_abs())
+                    .sum::<u64>()
+            );
+            let a: Vec<u16> = (0..len).map(|_| rng.gen()).collect();
+            let b: Vec<u16> = (0..len).map(|_| rng.gen()).collect();
+            assert_eq!(
+                sad_u16(&a, &b),
+                a.iter()
+                    .zip(&b)
+                    .map(|(&a, &b)| (a as i64 - b as i64).unsigned_abs())
+                    .sum::<u64>()
+            );
+        }
+    }
+}
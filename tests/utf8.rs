@@ -0,0 +1,73 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use swar::*;
+
+fn assert_matches_from_utf8(bytes: &[u8]) {
+    let expected = core::str::from_utf8(bytes).map(|_| ());
+    let actual = validate_utf8(bytes);
+    assert_eq!(
+        actual.map_err(|e| (e.valid_up_to(), e.error_len())),
+        expected.map_err(|e| (e.valid_up_to(), e.error_len())),
+        "{:X?}",
+        bytes
+    );
+    assert_eq!(is_ascii(bytes), bytes.is_ascii());
+}
+
+#[test]
+fn short_sequences_match_from_utf8() {
+    // Every first byte and the second bytes around each range boundary, followed
+    // by continuation bytes or ASCII, after ASCII that ends at offsets across a word.
+    let tails: [&[u8]; 3] = [b"", b"\x80", b"a"];
+    for prefix in [0, 1, 13, 15, 16, 30] {
+        for first in 0x7F..=0xFFu8 {
+            for second in [None, Some(0x7F), Some(0x80), Some(0x8F), Some(0x90)]
+                .iter()
+                .copied()
+                .chain((0x9F..=0xC0).map(Some))
+            {
+                for tail in tails.iter() {
+                    let mut bytes = vec![b'a'; prefix];
+                    bytes.push(first);
+                    bytes.extend(second);
+                    if second.is_some() {
+                        bytes.extend_from_slice(tail);
+                        assert_matches_from_utf8(&bytes);
+                        bytes.extend_from_slice(tail);
+                    }
+                    assert_matches_from_utf8(&bytes);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn random_bytes_match_from_utf8() {
+    let mut rng = SmallRng::from_seed([10; 16]);
+    let pieces: [&[u8]; 12] = [
+        b"a",
+        b"0123456789abcdef",
+        "\u{E9}".as_bytes(),
+        "\u{20AC}".as_bytes(),
+        "\u{FFFF}".as_bytes(),
+        "\u{1F600}".as_bytes(),
+        "\u{10FFFF}".as_bytes(),
+        b"\x80",
+        b"\xC0\x80",
+        b"\xED\xA0\x80",
+        b"\xF4\x90\x80\x80",
+        b"\xFF",
+    ];
+    for _ in 0..100_000 {
+        let len = rng.gen_range(0, 40);
+        let mut bytes: Vec<u8> = (0..len)
+            .flat_map(|_| pieces[rng.gen_range(0, pieces.len())].iter().copied())
+            .collect();
+        // Cut a valid sequence short some of the time.
+        if rng.gen() {
+            bytes.truncate(rng.gen_range(0, bytes.len() + 1));
+        }
+        assert_matches_from_utf8(&bytes);
+    }
+}